@@ -70,6 +70,60 @@ pub fn upper_bound<T>(container: &[T], key: &T) -> Option<usize>
     }
 }
 
+/// Returns the first position with an item not less than the key
+/// (the position to insert the key keeping the order, the key goes before the equal items).
+/// It is necessary that the container is pre-sorted
+///```
+/// use librualg::binary_search::lower_bound_index;
+///
+/// let seq = vec![1, 2, 3, 4, 5, 8, 8, 8, 9, 20];
+///
+/// assert_eq!(lower_bound_index(&seq, &8), 5);
+/// assert_eq!(lower_bound_index(&seq, &7), 5);
+/// assert_eq!(lower_bound_index(&seq, &21), 10);
+/// ```
+pub fn lower_bound_index<T>(container: &[T], key: &T) -> usize
+    where T: std::cmp::Ord {
+    let mut l = 0;
+    let mut r = container.len();
+    while l < r {
+        let idx = l + (r - l) / 2;
+        if container[idx] < *key {
+            l = idx + 1;
+        } else {
+            r = idx;
+        }
+    }
+    l
+}
+
+/// Returns the first position with an item greater than the key
+/// (the position to insert the key keeping the order, the key goes after the equal items).
+/// It is necessary that the container is pre-sorted
+///```
+/// use librualg::binary_search::upper_bound_index;
+///
+/// let seq = vec![1, 2, 3, 4, 5, 8, 8, 8, 9, 20];
+///
+/// assert_eq!(upper_bound_index(&seq, &8), 8);
+/// assert_eq!(upper_bound_index(&seq, &7), 5);
+/// assert_eq!(upper_bound_index(&seq, &0), 0);
+/// ```
+pub fn upper_bound_index<T>(container: &[T], key: &T) -> usize
+    where T: std::cmp::Ord {
+    let mut l = 0;
+    let mut r = container.len();
+    while l < r {
+        let idx = l + (r - l) / 2;
+        if container[idx] <= *key {
+            l = idx + 1;
+        } else {
+            r = idx;
+        }
+    }
+    l
+}
+
 #[test]
 fn test_lower_bound(){
     let seq = vec![1, 2, 3, 4, 5, 8, 8, 8, 9, 20];
@@ -93,4 +147,14 @@ fn test_empty_container(){
     let seq = vec![];
     assert_eq!(upper_bound(&seq, &1), None);
     assert_eq!(lower_bound(&seq, &1), None);
-}
\ No newline at end of file
+}
+#[test]
+fn test_bound_index(){
+    let seq = vec![1, 2, 3, 4, 5, 8, 8, 8, 9, 20];
+    for key in 0..22 {
+        assert_eq!(lower_bound_index(&seq, &key), seq.iter().filter(|x| **x < key).count());
+        assert_eq!(upper_bound_index(&seq, &key), seq.iter().filter(|x| **x <= key).count());
+    }
+    assert_eq!(lower_bound_index(&[], &1), 0);
+    assert_eq!(upper_bound_index(&[], &1), 0);
+}
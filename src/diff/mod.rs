@@ -0,0 +1,514 @@
+use std::collections::BTreeMap;
+use crate::string::lis;
+
+/// Operation of a diff between the sequences `old` and `new`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    /// `old[i]` equals `new[j]`
+    Equal(usize, usize),
+    /// `old[i]` is deleted
+    Delete(usize),
+    /// `new[j]` is inserted
+    Insert(usize),
+}
+
+/// Shortest edit script (Myers' O(ND) algorithm): a longest common subsequence is kept,
+/// the rest of `old` is deleted and the rest of `new` is inserted.
+/// Uses the linear space refinement (divide and conquer on the middle snake): O((N + M) D) time, O(N + M) memory.
+///```
+/// use librualg::diff::{myers, DiffOp};
+///
+/// let old = ["a", "b", "c", "d"];
+/// let new = ["a", "c", "d", "e"];
+/// assert_eq!(myers(&old, &new), vec![
+///     DiffOp::Equal(0, 0), DiffOp::Delete(1), DiffOp::Equal(2, 1), DiffOp::Equal(3, 2), DiffOp::Insert(3)
+/// ]);
+/// ```
+pub fn myers<T: Eq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let mut ops = vec![];
+    myers_with_offsets(old, new, 0, 0, &mut ops);
+    ops
+}
+
+fn myers_with_offsets<T: Eq>(old: &[T], new: &[T], offset_old: usize, offset_new: usize, ops: &mut Vec<DiffOp>) {
+    let mut prefix = 0;
+    while prefix < old.len() && prefix < new.len() && old[prefix] == new[prefix] {
+        ops.push(DiffOp::Equal(offset_old + prefix, offset_new + prefix));
+        prefix += 1;
+    }
+    let mut suffix = 0;
+    while suffix < old.len() - prefix && suffix < new.len() - prefix && old[old.len() - 1 - suffix] == new[new.len() - 1 - suffix] {
+        suffix += 1;
+    }
+    let (old_mid, new_mid) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+    let (offset_old_mid, offset_new_mid) = (offset_old + prefix, offset_new + prefix);
+    if old_mid.is_empty() {
+        ops.extend((0..new_mid.len()).map(|j| DiffOp::Insert(offset_new_mid + j)));
+    } else if new_mid.is_empty() {
+        ops.extend((0..old_mid.len()).map(|i| DiffOp::Delete(offset_old_mid + i)));
+    } else {
+        // both halves have at most half of the edits, so the recursion depth is O(log D)
+        let (x, y, u, v) = middle_snake(old_mid, new_mid);
+        myers_with_offsets(&old_mid[..x], &new_mid[..y], offset_old_mid, offset_new_mid, ops);
+        ops.extend((0..u - x).map(|k| DiffOp::Equal(offset_old_mid + x + k, offset_new_mid + y + k)));
+        myers_with_offsets(&old_mid[u..], &new_mid[v..], offset_old_mid + u, offset_new_mid + v, ops);
+    }
+    for k in (0..suffix).rev() {
+        ops.push(DiffOp::Equal(old.len() - 1 - k + offset_old, new.len() - 1 - k + offset_new));
+    }
+}
+
+/// The middle snake of a shortest edit script (Myers' linear space refinement):
+/// the forward and the backward searches meet on it. Returns its start `(x, y)` and end `(u, v)`.
+fn middle_snake<T: Eq>(old: &[T], new: &[T]) -> (usize, usize, usize, usize) {
+    let n = old.len() as isize;
+    let m = new.len() as isize;
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max = (n + m + 1) / 2;
+    let offset = max + 1;
+    // forward[k]: the furthest x on the diagonal k = x - y from (0, 0),
+    // backward[k]: the furthest x on the diagonal k of the reversed sequences
+    let mut forward = vec![0isize; (2 * offset + 1) as usize];
+    let mut backward = vec![0isize; (2 * offset + 1) as usize];
+    for d in 0..=max {
+        let mut k = -d;
+        while k <= d {
+            let idx = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && forward[idx - 1] < forward[idx + 1]) { forward[idx + 1] } else { forward[idx - 1] + 1 };
+            let mut y = x - k;
+            let (start_x, start_y) = (x, y);
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[idx] = x;
+            let rk = delta - k;
+            if odd && rk > -d && rk < d && x + backward[(offset + rk) as usize] >= n {
+                return (start_x as usize, start_y as usize, x as usize, y as usize);
+            }
+            k += 2;
+        }
+        let mut k = -d;
+        while k <= d {
+            let idx = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && backward[idx - 1] < backward[idx + 1]) { backward[idx + 1] } else { backward[idx - 1] + 1 };
+            let mut y = x - k;
+            let (start_x, start_y) = (x, y);
+            while x < n && y < m && old[(n - 1 - x) as usize] == new[(m - 1 - y) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[idx] = x;
+            let fk = delta - k;
+            if !odd && fk >= -d && fk <= d && x + forward[(offset + fk) as usize] >= n {
+                return ((n - x) as usize, (m - y) as usize, (n - start_x) as usize, (m - start_y) as usize);
+            }
+            k += 2;
+        }
+    }
+    unreachable!("the searches always meet")
+}
+
+/// Patience diff: the lines occurring exactly once in both sequences are matched first
+/// (by the longest increasing subsequence), the gaps between them are diffed recursively.
+/// Gives more readable diffs for source code and configuration files.
+///```
+/// use librualg::diff::{patience, DiffOp};
+///
+/// let old = ["a", "b", "c"];
+/// let new = ["c", "a", "b"];
+/// assert_eq!(patience(&old, &new), vec![
+///     DiffOp::Insert(0), DiffOp::Equal(0, 1), DiffOp::Equal(1, 2), DiffOp::Delete(2)
+/// ]);
+/// ```
+pub fn patience<T: Ord>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let mut ops = vec![];
+    patience_rec(old, new, 0, 0, &mut ops);
+    ops
+}
+
+fn patience_rec<T: Ord>(old: &[T], new: &[T], offset_old: usize, offset_new: usize, ops: &mut Vec<DiffOp>) {
+    let mut prefix = 0;
+    while prefix < old.len() && prefix < new.len() && old[prefix] == new[prefix] {
+        ops.push(DiffOp::Equal(offset_old + prefix, offset_new + prefix));
+        prefix += 1;
+    }
+    let mut suffix = 0;
+    while suffix < old.len() - prefix && suffix < new.len() - prefix && old[old.len() - 1 - suffix] == new[new.len() - 1 - suffix] {
+        suffix += 1;
+    }
+    let (old_mid, new_mid) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+    let (offset_old_mid, offset_new_mid) = (offset_old + prefix, offset_new + prefix);
+
+    // (count in old, position in old, count in new, position in new)
+    let mut lines: BTreeMap<&T, (usize, usize, usize, usize)> = BTreeMap::new();
+    for (i, line) in old_mid.iter().enumerate() {
+        let entry = lines.entry(line).or_insert((0, 0, 0, 0));
+        entry.0 += 1;
+        entry.1 = i;
+    }
+    for (j, line) in new_mid.iter().enumerate() {
+        if let Some(entry) = lines.get_mut(line) {
+            entry.2 += 1;
+            entry.3 = j;
+        }
+    }
+    let mut unique: Vec<(usize, usize)> = lines.values().filter(|entry| entry.0 == 1 && entry.2 == 1).map(|entry| (entry.1, entry.3)).collect();
+    if unique.is_empty() {
+        myers_with_offsets(old_mid, new_mid, offset_old_mid, offset_new_mid, ops);
+    } else {
+        unique.sort_unstable();
+        let (mut i, mut j) = (0, 0);
+        let positions: Vec<usize> = unique.iter().map(|pair| pair.1).collect();
+        for (a, b) in lis(&positions).into_iter().map(|idx| unique[idx]) {
+            patience_rec(&old_mid[i..a], &new_mid[j..b], offset_old_mid + i, offset_new_mid + j, ops);
+            ops.push(DiffOp::Equal(offset_old_mid + a, offset_new_mid + b));
+            i = a + 1;
+            j = b + 1;
+        }
+        patience_rec(&old_mid[i..], &new_mid[j..], offset_old_mid + i, offset_new_mid + j, ops);
+    }
+    for k in (0..suffix).rev() {
+        ops.push(DiffOp::Equal(old.len() - 1 - k + offset_old, new.len() - 1 - k + offset_new));
+    }
+}
+
+/// Group of changes with the surrounding context
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+    pub ops: Vec<DiffOp>,
+}
+
+/// Splits an edit script into hunks with `context` equal elements around the changes.
+/// The changes separated by at most `2 * context` equal elements get into one hunk.
+///```
+/// use librualg::diff::{myers, hunks};
+///
+/// let old = ["1", "2", "3", "4", "5", "6", "7", "8"];
+/// let new = ["1", "2", "x", "4", "5", "6", "7", "y"];
+/// let res = hunks(&myers(&old, &new), 1);
+/// assert_eq!(res.len(), 2);
+/// assert_eq!((res[0].old_start, res[0].old_len, res[0].new_start, res[0].new_len), (1, 3, 1, 3));
+/// assert_eq!((res[1].old_start, res[1].old_len, res[1].new_start, res[1].new_len), (6, 2, 6, 2));
+/// ```
+pub fn hunks(ops: &[DiffOp], context: usize) -> Vec<Hunk> {
+    let changes: Vec<usize> = (0..ops.len()).filter(|idx| !matches!(ops[*idx], DiffOp::Equal(_, _))).collect();
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for idx in changes {
+        let start = idx.saturating_sub(context);
+        let end = (idx + 1 + context).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    // positions in `old` and `new` before every operation
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut i, mut j) = (0, 0);
+    for op in ops {
+        positions.push((i, j));
+        match op {
+            DiffOp::Equal(_, _) => {
+                i += 1;
+                j += 1;
+            }
+            DiffOp::Delete(_) => i += 1,
+            DiffOp::Insert(_) => j += 1,
+        }
+    }
+    positions.push((i, j));
+    ranges.into_iter().map(|(start, end)| Hunk {
+        old_start: positions[start].0,
+        old_len: positions[end].0 - positions[start].0,
+        new_start: positions[start].1,
+        new_len: positions[end].1 - positions[start].1,
+        ops: ops[start..end].to_vec(),
+    }).collect()
+}
+
+fn header_range(start: usize, len: usize) -> String {
+    let start = if len == 0 { start } else { start + 1 };
+    if len == 1 {
+        format!("{}", start)
+    } else {
+        format!("{},{}", start, len)
+    }
+}
+
+// lines with their terminators, so `\r\n` and a missing final newline are compared and restored
+fn split_lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// Unified diff of two texts by lines (Myers' algorithm) with `context` lines around the changes.
+/// Returns an empty string for equal texts. The line endings are compared, the lines keep them as is
+/// and a last line without the newline is followed by `\ No newline at end of file`.
+///```
+/// use librualg::diff::unified_diff;
+///
+/// let old = "host = localhost\nport = 80\nuser = admin\n";
+/// let new = "host = localhost\nport = 8080\nuser = admin\n";
+/// assert_eq!(unified_diff("a/app.conf", "b/app.conf", old, new, 3),
+///     "--- a/app.conf\n+++ b/app.conf\n@@ -1,3 +1,3 @@\n host = localhost\n-port = 80\n+port = 8080\n user = admin\n");
+///
+/// assert_eq!(unified_diff("a", "b", "x\ny", "x\ny\n", 3),
+///     "--- a\n+++ b\n@@ -1,2 +1,2 @@\n x\n-y\n\\ No newline at end of file\n+y\n");
+/// ```
+pub fn unified_diff(old_name: &str, new_name: &str, old: &str, new: &str, context: usize) -> String {
+    let old_lines = split_lines(old);
+    let new_lines = split_lines(new);
+    let hunks = hunks(&myers(&old_lines, &new_lines), context);
+    if hunks.is_empty() {
+        return String::new();
+    }
+    let mut res = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for hunk in hunks {
+        res += &format!("@@ -{} +{} @@\n", header_range(hunk.old_start, hunk.old_len), header_range(hunk.new_start, hunk.new_len));
+        for op in hunk.ops {
+            let (marker, line) = match op {
+                DiffOp::Equal(i, _) => (' ', old_lines[i]),
+                DiffOp::Delete(i) => ('-', old_lines[i]),
+                DiffOp::Insert(j) => ('+', new_lines[j]),
+            };
+            res.push(marker);
+            res += line;
+            if !line.ends_with('\n') {
+                res += "\n\\ No newline at end of file\n";
+            }
+        }
+    }
+    res
+}
+
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    let mut parts = range.splitn(2, ',');
+    let start = parts.next()?.parse::<usize>().ok()?;
+    let len = match parts.next() {
+        Some(len) => len.parse::<usize>().ok()?,
+        None => 1,
+    };
+    if len == 0 {
+        Some((start, 0))
+    } else if start > 0 {
+        Some((start - 1, len))
+    } else {
+        None
+    }
+}
+
+/// Applies a unified diff to the text.
+/// Returns `None` if the patch is malformed or does not match the text.
+/// The line endings are taken from the text and the patch, `\ No newline at end of file` drops the newline of the line before it.
+///```
+/// use librualg::diff::{unified_diff, apply_patch};
+///
+/// let old = "a\nb\nc\nd\n";
+/// let new = "a\nc\nd\ne\n";
+/// let patch = unified_diff("old", "new", old, new, 1);
+/// assert_eq!(apply_patch(old, &patch), Some(new.to_string()));
+/// assert_eq!(apply_patch("a\nx\nc\nd\n", &patch), None);
+/// let patch = unified_diff("old", "new", "x\r\ny\r\n", "x\r\nz\r\n", 1);
+/// assert_eq!(apply_patch("x\r\ny\r\n", &patch), Some("x\r\nz\r\n".to_string()));
+/// ```
+pub fn apply_patch(text: &str, patch: &str) -> Option<String> {
+    let old = split_lines(text);
+    let mut res: Vec<&str> = vec![];
+    let mut cursor = 0;
+    let mut header = true;
+    let mut lines = split_lines(patch).into_iter().peekable();
+    while let Some(line) = lines.next() {
+        if !line.starts_with("@@") {
+            // file names and other lines before the first hunk are skipped
+            if header || line.starts_with("--- ") || line.starts_with("+++ ") {
+                continue;
+            }
+            return None;
+        }
+        header = false;
+        let mut parts = line.split_whitespace().skip(1);
+        let (old_start, old_len) = parse_range(parts.next()?.strip_prefix('-')?)?;
+        let (_, new_len) = parse_range(parts.next()?.strip_prefix('+')?)?;
+        if old_start < cursor || old_start > old.len() {
+            return None;
+        }
+        res.extend(&old[cursor..old_start]);
+        cursor = old_start;
+        let (mut old_count, mut new_count) = (0, 0);
+        while old_count < old_len || new_count < new_len {
+            let line = lines.next()?;
+            if line.starts_with('\\') {
+                continue;
+            }
+            let (kind, mut content) = if line == "\n" || line == "\r\n" {
+                // an empty context line without the leading space
+                (b' ', line)
+            } else {
+                let kind = line.as_bytes()[0];
+                if !matches!(kind, b' ' | b'-' | b'+') {
+                    return None;
+                }
+                (kind, line.split_at(1).1)
+            };
+            if matches!(lines.peek(), Some(next) if next.starts_with('\\')) {
+                content = content.strip_suffix('\n').unwrap_or(content);
+                lines.next();
+            }
+            match kind {
+                b' ' | b'-' => {
+                    if old.get(cursor) != Some(&content) {
+                        return None;
+                    }
+                    cursor += 1;
+                    old_count += 1;
+                    if kind == b' ' {
+                        res.push(content);
+                        new_count += 1;
+                    }
+                }
+                b'+' => {
+                    res.push(content);
+                    new_count += 1;
+                }
+                _ => return None,
+            }
+        }
+        if old_count != old_len || new_count != new_len {
+            return None;
+        }
+        while let Some(line) = lines.peek() {
+            if !line.starts_with('\\') {
+                break;
+            }
+            lines.next();
+        }
+    }
+    res.extend(&old[cursor..]);
+    Some(res.concat())
+}
+
+#[cfg(test)]
+fn check_ops<T: Eq>(old: &[T], new: &[T], ops: &[DiffOp]) -> usize {
+    let (mut i, mut j) = (0, 0);
+    let mut changes = 0;
+    for op in ops {
+        match *op {
+            DiffOp::Equal(x, y) => {
+                assert!(x == i && y == j && old[x] == new[y]);
+                i += 1;
+                j += 1;
+            }
+            DiffOp::Delete(x) => {
+                assert_eq!(x, i);
+                i += 1;
+                changes += 1;
+            }
+            DiffOp::Insert(y) => {
+                assert_eq!(y, j);
+                j += 1;
+                changes += 1;
+            }
+        }
+    }
+    assert_eq!((i, j), (old.len(), new.len()));
+    changes
+}
+
+#[test]
+fn test_myers() {
+    let pairs = [("", ""), ("", "abc"), ("abc", ""), ("abcabba", "cbabac"), ("kitten", "sitting"), ("aaaa", "aaaa"), ("abcdef", "fedcba")];
+    for (a, b) in &pairs {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        let changes = check_ops(a, b, &myers(a, b));
+        // the number of changes is n + m - 2 * lcs
+        assert_eq!(changes, a.len() + b.len() - 2 * crate::string::lcs_length(a, b));
+        check_ops(a, b, &patience(a, b));
+    }
+    assert_eq!(myers(b"abcabba", b"cbabac").len(), 9);
+}
+
+#[test]
+fn test_myers_linear_space() {
+    use rand::{Rng, SeedableRng, StdRng};
+
+    let mut rng = StdRng::from_seed(&[37]);
+    for _ in 0..300 {
+        let a: Vec<u8> = (0..rng.gen_range(0, 30)).map(|_| rng.gen_range(0, 3)).collect();
+        let b: Vec<u8> = (0..rng.gen_range(0, 30)).map(|_| rng.gen_range(0, 3)).collect();
+        let changes = check_ops(&a, &b, &myers(&a, &b));
+        assert_eq!(changes, a.len() + b.len() - 2 * crate::string::lcs_length(&a, &b));
+    }
+    // completely different sequences: D = n + m, the memory stays linear
+    let old: Vec<u32> = (0..2000).collect();
+    let new: Vec<u32> = (2000..4000).collect();
+    let ops = myers(&old, &new);
+    assert_eq!(check_ops(&old, &new, &ops), 4000);
+    // long files with a few changes
+    let old: Vec<u32> = (0..100_000).collect();
+    let mut new = old.clone();
+    new[10] = 1_000_000;
+    new.remove(50_000);
+    new.insert(90_000, 7);
+    assert_eq!(check_ops(&old, &new, &myers(&old, &new)), 4);
+}
+
+#[test]
+fn test_patience() {
+    let old = ["fn a() {", "}", "", "fn b() {", "}"];
+    let new = ["fn a() {", "}", "", "fn c() {", "}", "", "fn b() {", "}"];
+    let ops = patience(&old, &new);
+    check_ops(&old, &new, &ops);
+    assert_eq!(ops.iter().filter(|op| matches!(op, DiffOp::Insert(_))).count(), 3);
+    assert_eq!(ops[3], DiffOp::Insert(3));
+}
+
+#[test]
+fn test_unified_diff_and_patch() {
+    let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+    let new = "0\n1\n2\n3\n4\n6\n7\n8\n9\n10\n11\n";
+    for context in 0..5 {
+        let patch = unified_diff("a", "b", old, new, context);
+        assert_eq!(apply_patch(old, &patch), Some(new.to_string()));
+        let back = unified_diff("b", "a", new, old, context);
+        assert_eq!(apply_patch(new, &back), Some(old.to_string()));
+    }
+    assert_eq!(unified_diff("a", "b", old, new, 1),
+        "--- a\n+++ b\n@@ -1 +1,2 @@\n+0\n 1\n@@ -4,3 +5,2 @@\n 4\n-5\n 6\n@@ -10 +10,2 @@\n 10\n+11\n");
+    assert_eq!(unified_diff("a", "b", old, old, 3), "");
+    assert_eq!(apply_patch(old, ""), Some(old.to_string()));
+    assert_eq!(apply_patch("", &unified_diff("a", "b", "", "x\ny\n", 3)), Some("x\ny\n".to_string()));
+    assert_eq!(apply_patch("x\ny\n", &unified_diff("a", "b", "x\ny\n", "", 3)), Some("".to_string()));
+    assert_eq!(apply_patch(old, "@@ -1,2 +1,2 @@\n 1\n-3\n+4\n"), None);
+    assert_eq!(apply_patch(old, "@@ -1,2 +1,2 @@\n 1\n"), None);
+    assert_eq!(apply_patch(old, "@@ garbage @@\n"), None);
+}
+
+#[test]
+fn test_unified_diff_line_endings() {
+    // the missing final newline is a change
+    let patch = unified_diff("a", "b", "x\ny", "x\ny\n", 3);
+    assert_eq!(patch, "--- a\n+++ b\n@@ -1,2 +1,2 @@\n x\n-y\n\\ No newline at end of file\n+y\n");
+    assert_eq!(apply_patch("x\ny", &patch), Some("x\ny\n".to_string()));
+    let patch = unified_diff("a", "b", "x\ny\n", "x\nz", 3);
+    assert_eq!(patch, "--- a\n+++ b\n@@ -1,2 +1,2 @@\n x\n-y\n+z\n\\ No newline at end of file\n");
+    assert_eq!(apply_patch("x\ny\n", &patch), Some("x\nz".to_string()));
+    // the new text decides the final newline, not the old one
+    let patch = unified_diff("a", "b", "x\ny", "x\nz", 3);
+    assert_eq!(apply_patch("x\ny", &patch), Some("x\nz".to_string()));
+    assert_eq!(apply_patch("x\ny\n", &patch), None);
+    let patch = unified_diff("a", "b", "a\nb\nc\nd\ne", "a\nB\nc\nd\ne", 1);
+    assert_eq!(apply_patch("a\nb\nc\nd\ne", &patch), Some("a\nB\nc\nd\ne".to_string()));
+    // CRLF lines stay CRLF
+    let old = "x\r\ny\r\n";
+    let new = "x\r\nz\r\n";
+    let patch = unified_diff("a", "b", old, new, 3);
+    assert_eq!(patch, "--- a\n+++ b\n@@ -1,2 +1,2 @@\n x\r\n-y\r\n+z\r\n");
+    assert_eq!(apply_patch(old, &patch), Some(new.to_string()));
+    assert_eq!(apply_patch("x\ny\n", &patch), None);
+    let patch = unified_diff("a", "b", "x\ny\n", "x\r\ny\r\n", 0);
+    assert_eq!(apply_patch("x\ny\n", &patch), Some("x\r\ny\r\n".to_string()));
+}
@@ -0,0 +1,396 @@
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
+use std::cmp::Ordering;
+use super::Graph;
+
+impl <Indent> Graph <Indent> where Indent: Eq + Ord + Clone {
+    /// PageRank algorithm.
+    /// Returns the rank of each vertex, the ranks sum up to 1.
+    /// The rank of dangling vertices (without outgoing edges) is distributed evenly over all vertices.
+    /// # Arguments
+    /// * `damping` - damping factor (usually 0.85)
+    /// * `tolerance` - the iterations stop when the L1 distance between two successive rank vectors is below this value
+    /// * `max_iterations` - upper bound of the number of iterations
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge("a", "b", 1.0);
+    /// graph.add_oriented_edge("b", "c", 1.0);
+    /// graph.add_oriented_edge("c", "a", 1.0);
+    /// graph.add_oriented_edge("d", "c", 1.0);
+    ///
+    /// let ranks = graph.page_rank(0.85, 1e-9, 100);
+    /// assert!(ranks["c"] > ranks["b"]);
+    /// assert!(ranks["b"] > ranks["d"]);
+    /// assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-6);
+    /// ```
+    pub fn page_rank(&self, damping: f64, tolerance: f64, max_iterations: usize) -> BTreeMap<Indent, f64> {
+        let (vertices, adj) = self.indexed();
+        let n = vertices.len();
+        if n == 0 {
+            return BTreeMap::new();
+        }
+        let mut rank = vec![1.0 / n as f64; n];
+        for _ in 0..max_iterations {
+            let mut dangling = 0.0;
+            for (v, edges) in adj.iter().enumerate() {
+                if edges.is_empty() {
+                    dangling += rank[v];
+                }
+            }
+            let base = (1.0 - damping) / n as f64 + damping * dangling / n as f64;
+            let mut next = vec![base; n];
+            for (v, edges) in adj.iter().enumerate() {
+                let share = damping * rank[v] / edges.len() as f64;
+                for (to, _) in edges {
+                    next[*to] += share;
+                }
+            }
+            let diff: f64 = next.iter().zip(rank.iter()).map(|(a, b)| (a - b).abs()).sum();
+            rank = next;
+            if diff < tolerance {
+                break;
+            }
+        }
+        vertices.into_iter().zip(rank).collect()
+    }
+
+    /// Betweenness centrality (Brandes algorithm), the paths are searched by BFS and the weights are ignored.
+    /// Every ordered pair of vertices is taken into account,
+    /// so for an undirected graph (edges in both directions) the values are twice the undirected ones.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge(1, 2, 1.0);
+    /// graph.add_oriented_edge(2, 3, 1.0);
+    /// graph.add_oriented_edge(3, 4, 1.0);
+    ///
+    /// let values = graph.betweenness_centrality();
+    /// assert_eq!(values[&1], 0.0);
+    /// assert_eq!(values[&2], 2.0);
+    /// assert_eq!(values[&3], 2.0);
+    /// assert_eq!(values[&4], 0.0);
+    /// ```
+    pub fn betweenness_centrality(&self) -> BTreeMap<Indent, f64> {
+        let (vertices, adj) = self.indexed();
+        let n = vertices.len();
+        let mut centrality = vec![0.0; n];
+        for s in 0..n {
+            let mut stack = Vec::with_capacity(n);
+            let mut preds = vec![vec![]; n];
+            let mut sigma = vec![0.0; n];
+            let mut dist: Vec<Option<usize>> = vec![None; n];
+            let mut queue = VecDeque::new();
+            sigma[s] = 1.0;
+            dist[s] = Some(0);
+            queue.push_back(s);
+            while let Some(v) = queue.pop_front() {
+                stack.push(v);
+                let d = dist[v].unwrap();
+                for (w, _) in &adj[v] {
+                    if dist[*w].is_none() {
+                        dist[*w] = Some(d + 1);
+                        queue.push_back(*w);
+                    }
+                    if dist[*w] == Some(d + 1) {
+                        sigma[*w] += sigma[v];
+                        preds[*w].push(v);
+                    }
+                }
+            }
+            accumulate(s, &stack, &preds, &sigma, &mut centrality);
+        }
+        vertices.into_iter().zip(centrality).collect()
+    }
+
+    /// Betweenness centrality (Brandes algorithm), the shortest paths are searched by Dijkstra algorithm.
+    /// The weights must be non-negative.
+    /// `dijkstra` keeps one parent per vertex, while Brandes algorithm needs all the predecessors on the shortest paths,
+    /// the number of the shortest paths and the order in which the vertices are settled, so the search is done here.
+    /// The path lengths are compared with a relative tolerance, so the ties are not lost to the rounding of `f32` weights.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge('a', 'b', 1.0);
+    /// graph.add_oriented_edge('b', 'c', 1.0);
+    /// graph.add_oriented_edge('a', 'c', 5.0);
+    ///
+    /// let values = graph.weighted_betweenness_centrality();
+    /// assert_eq!(values[&'b'], 1.0);
+    /// assert_eq!(values[&'a'], 0.0);
+    /// ```
+    pub fn weighted_betweenness_centrality(&self) -> BTreeMap<Indent, f64> {
+        struct D {
+            node: usize,
+            dist: f64,
+        }
+
+        impl std::cmp::PartialEq for D {
+            fn eq(&self, other: &D) -> bool {
+                self.dist == other.dist
+            }
+        }
+
+        impl Eq for D {}
+
+        impl std::cmp::Ord for D {
+            fn cmp(&self, other: &Self) -> Ordering {
+                other.dist.partial_cmp(&self.dist).unwrap()
+            }
+        }
+
+        impl std::cmp::PartialOrd for D {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        let (vertices, adj) = self.indexed();
+        let n = vertices.len();
+        let mut centrality = vec![0.0; n];
+        for s in 0..n {
+            let mut stack = Vec::with_capacity(n);
+            let mut preds = vec![vec![]; n];
+            let mut sigma = vec![0.0; n];
+            let mut dist: Vec<Option<f64>> = vec![None; n];
+            let mut visited = vec![false; n];
+            let mut heap = BinaryHeap::new();
+            sigma[s] = 1.0;
+            dist[s] = Some(0.0);
+            heap.push(D { node: s, dist: 0.0 });
+            while let Some(D { node: v, dist: d }) = heap.pop() {
+                if visited[v] {
+                    continue;
+                }
+                visited[v] = true;
+                stack.push(v);
+                for (w, weight) in &adj[v] {
+                    let alt = d + *weight as f64;
+                    match dist[*w] {
+                        Some(value) if is_tie(alt, value) => {
+                            if visited[*w] {
+                                continue;
+                            }
+                            sigma[*w] += sigma[v];
+                            preds[*w].push(v);
+                        }
+                        Some(value) if alt > value => {}
+                        _ if !visited[*w] => {
+                            dist[*w] = Some(alt);
+                            sigma[*w] = sigma[v];
+                            preds[*w] = vec![v];
+                            heap.push(D { node: *w, dist: alt });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            accumulate(s, &stack, &preds, &sigma, &mut centrality);
+        }
+        vertices.into_iter().zip(centrality).collect()
+    }
+
+    /// Closeness centrality: the number of reachable vertices divided by the sum of the distances to them
+    /// (distances are calculated by Dijkstra algorithm).
+    /// The value is scaled by the share of reachable vertices (Wasserman and Faust),
+    /// so the vertices of small components do not get inflated values.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge(1, 2, 1.0);
+    /// graph.add_oriented_edge(1, 3, 1.0);
+    /// graph.add_oriented_edge(2, 3, 1.0);
+    ///
+    /// let values = graph.closeness_centrality();
+    /// assert_eq!(values[&1], 1.0);
+    /// assert_eq!(values[&2], 0.5);
+    /// assert_eq!(values[&3], 0.0);
+    /// ```
+    pub fn closeness_centrality(&self) -> BTreeMap<Indent, f64> {
+        let (vertices, _) = self.indexed();
+        let n = vertices.len();
+        let mut res = BTreeMap::new();
+        for vertex in vertices {
+            let (_, distances) = self.dijkstra(vertex.clone());
+            let reachable = (distances.len() - 1) as f64;
+            let total: f64 = distances.values().map(|value| *value as f64).sum();
+            let value = if total > 0.0 {
+                (reachable / total) * (reachable / (n - 1) as f64)
+            } else {
+                0.0
+            };
+            res.insert(vertex, value);
+        }
+        res
+    }
+
+    /// Degree centrality: the number of outgoing edges divided by `n - 1`.
+    /// For an undirected graph (edges in both directions) it is the usual degree centrality.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge(1, 2, 0.0);
+    /// graph.add_oriented_edge(1, 3, 0.0);
+    /// graph.add_oriented_edge(2, 3, 0.0);
+    ///
+    /// let values = graph.degree_centrality();
+    /// assert_eq!(values[&1], 1.0);
+    /// assert_eq!(values[&2], 0.5);
+    /// assert_eq!(values[&3], 0.0);
+    /// ```
+    pub fn degree_centrality(&self) -> BTreeMap<Indent, f64> {
+        let (vertices, adj) = self.indexed();
+        let scale = normalization(vertices.len());
+        vertices.into_iter().zip(adj.iter().map(|edges| edges.len() as f64 * scale)).collect()
+    }
+
+    /// In-degree centrality: the number of incoming edges divided by `n - 1`.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge(1, 2, 0.0);
+    /// graph.add_oriented_edge(1, 3, 0.0);
+    /// graph.add_oriented_edge(2, 3, 0.0);
+    ///
+    /// let values = graph.in_degree_centrality();
+    /// assert_eq!(values[&1], 0.0);
+    /// assert_eq!(values[&3], 1.0);
+    /// ```
+    pub fn in_degree_centrality(&self) -> BTreeMap<Indent, f64> {
+        let (vertices, adj) = self.indexed();
+        let scale = normalization(vertices.len());
+        let mut degrees = vec![0.0; vertices.len()];
+        for edges in &adj {
+            for (to, _) in edges {
+                degrees[*to] += scale;
+            }
+        }
+        vertices.into_iter().zip(degrees).collect()
+    }
+}
+
+fn normalization(n: usize) -> f64 {
+    if n > 1 {
+        1.0 / (n - 1) as f64
+    } else {
+        1.0
+    }
+}
+
+/// Dependency accumulation step of Brandes algorithm
+fn accumulate(s: usize, stack: &[usize], preds: &[Vec<usize>], sigma: &[f64], centrality: &mut [f64]) {
+    let mut delta = vec![0.0; sigma.len()];
+    for w in stack.iter().rev() {
+        for v in &preds[*w] {
+            delta[*v] += sigma[*v] / sigma[*w] * (1.0 + delta[*w]);
+        }
+        if *w != s {
+            centrality[*w] += delta[*w];
+        }
+    }
+}
+
+// the lengths of two paths are equal up to the rounding of the weights
+fn is_tie(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-6 * a.abs().max(b.abs()).max(1.0)
+}
+
+#[test]
+fn test_page_rank() {
+    let mut graph = Graph::new();
+    graph.add_oriented_edge(1, 2, 0.0);
+    graph.add_oriented_edge(2, 1, 0.0);
+    let ranks = graph.page_rank(0.85, 1e-12, 100);
+    assert!((ranks[&1] - 0.5).abs() < 1e-9);
+    assert!((ranks[&2] - 0.5).abs() < 1e-9);
+
+    let mut graph = Graph::new();
+    graph.add_oriented_edge(1, 2, 0.0);
+    graph.add_oriented_edge(1, 3, 0.0);
+    let ranks = graph.page_rank(0.85, 1e-12, 1000);
+    assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!((ranks[&2] - ranks[&3]).abs() < 1e-9);
+    assert!(ranks[&2] > ranks[&1]);
+
+    assert!(Graph::<u32>::new().page_rank(0.85, 1e-9, 100).is_empty());
+}
+
+#[test]
+fn test_betweenness_centrality() {
+    let mut graph = Graph::new();
+    for (a, b) in &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)] {
+        graph.add_oriented_edge(*a, *b, 1.0);
+        graph.add_oriented_edge(*b, *a, 1.0);
+    }
+    let values = graph.betweenness_centrality();
+    assert_eq!(values[&1], 1.0);
+    assert_eq!(values[&2], 2.0);
+    assert_eq!(values[&3], 2.0);
+    assert_eq!(values[&4], 7.0);
+    assert_eq!(values[&5], 0.0);
+    assert_eq!(values, graph.weighted_betweenness_centrality());
+}
+
+#[test]
+fn test_weighted_betweenness_centrality() {
+    let mut graph = Graph::new();
+    graph.add_oriented_edge(1, 2, 1.0);
+    graph.add_oriented_edge(2, 4, 1.0);
+    graph.add_oriented_edge(1, 3, 1.0);
+    graph.add_oriented_edge(3, 4, 1.0);
+    graph.add_oriented_edge(1, 4, 3.0);
+    let values = graph.weighted_betweenness_centrality();
+    assert_eq!(values[&2], 0.5);
+    assert_eq!(values[&3], 0.5);
+    assert_eq!(values[&1], 0.0);
+    let values = graph.betweenness_centrality();
+    assert_eq!(values[&2], 0.0);
+    assert_eq!(values[&3], 0.0);
+
+    // 0.1 + 0.2, 0.2 + 0.1 and 0.15 + 0.15 differ in floating point, the paths are still tied
+    let mut graph = Graph::new();
+    graph.add_oriented_edge(1, 2, 0.1);
+    graph.add_oriented_edge(2, 4, 0.2);
+    graph.add_oriented_edge(1, 3, 0.2);
+    graph.add_oriented_edge(3, 4, 0.1);
+    graph.add_oriented_edge(1, 5, 0.15);
+    graph.add_oriented_edge(5, 4, 0.15);
+    let values = graph.weighted_betweenness_centrality();
+    assert!((values[&2] - 1.0 / 3.0).abs() < 1e-9);
+    assert!((values[&3] - 1.0 / 3.0).abs() < 1e-9);
+    assert!((values[&5] - 1.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_closeness_centrality() {
+    let mut graph = Graph::new();
+    graph.add_oriented_edge(1, 2, 2.0);
+    graph.add_oriented_edge(2, 3, 2.0);
+    graph.add_oriented_edge(4, 5, 1.0);
+    let values = graph.closeness_centrality();
+    assert_eq!(values[&1], 2.0 / 6.0 * 0.5);
+    assert_eq!(values[&2], 0.5 * 0.25);
+    assert_eq!(values[&3], 0.0);
+    assert_eq!(values[&4], 0.25);
+}
+
+#[test]
+fn test_degree_centrality() {
+    let mut graph = Graph::new();
+    graph.add_oriented_edge("a", "b", 0.0);
+    graph.add_oriented_edge("a", "c", 0.0);
+    graph.add_oriented_edge("a", "d", 0.0);
+    graph.add_oriented_edge("b", "a", 0.0);
+    let values = graph.degree_centrality();
+    assert_eq!(values["a"], 1.0);
+    assert_eq!(values["b"], 1.0 / 3.0);
+    assert_eq!(values["c"], 0.0);
+    let values = graph.in_degree_centrality();
+    assert_eq!(values["a"], 1.0 / 3.0);
+    assert_eq!(values["d"], 1.0 / 3.0);
+}
@@ -0,0 +1,388 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::cmp::min;
+use super::Graph;
+
+impl <Indent> Graph <Indent> where Indent: Eq + Ord + Clone {
+    /// Undirected neighbour sets (the direction of the edges is ignored, loops are dropped)
+    fn undirected(&self) -> (Vec<Indent>, Vec<BTreeSet<usize>>) {
+        let (vertices, adj) = self.indexed();
+        let mut neighbours = vec![BTreeSet::new(); vertices.len()];
+        for (from, edges) in adj.iter().enumerate() {
+            for (to, _) in edges {
+                if from != *to {
+                    neighbours[from].insert(*to);
+                    neighbours[*to].insert(from);
+                }
+            }
+        }
+        (vertices, neighbours)
+    }
+
+    /// Greedy graph coloring (DSatur algorithm).
+    /// The next vertex to color is the one with the largest number of distinct colors among its neighbours.
+    /// The direction of the edges is ignored. Returns the color (starting from 0) of each vertex.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge(1, 2, 0.0);
+    /// graph.add_oriented_edge(2, 3, 0.0);
+    /// graph.add_oriented_edge(3, 1, 0.0);
+    /// graph.add_oriented_edge(3, 4, 0.0);
+    ///
+    /// let colors = graph.dsatur_coloring();
+    /// assert_eq!(colors.values().max(), Some(&2));
+    /// assert_ne!(colors[&3], colors[&4]);
+    /// ```
+    pub fn dsatur_coloring(&self) -> BTreeMap<Indent, usize> {
+        let (vertices, neighbours) = self.undirected();
+        let n = vertices.len();
+        let mut colors: Vec<Option<usize>> = vec![None; n];
+        let mut saturation = vec![BTreeSet::new(); n];
+        for _ in 0..n {
+            let vertex = (0..n)
+                .filter(|v| colors[*v].is_none())
+                .max_by(|a, b| {
+                    saturation[*a].len().cmp(&saturation[*b].len())
+                        .then(neighbours[*a].len().cmp(&neighbours[*b].len()))
+                        .then(b.cmp(a))
+                })
+                .unwrap();
+            let color = smallest_free_color(&saturation[vertex]);
+            colors[vertex] = Some(color);
+            for to in &neighbours[vertex] {
+                saturation[*to].insert(color);
+            }
+        }
+        vertices.into_iter().zip(colors.into_iter().map(|color| color.unwrap())).collect()
+    }
+
+    /// Greedy graph coloring (Welsh-Powell algorithm).
+    /// The vertices are colored in the order of decreasing degree.
+    /// The direction of the edges is ignored. Returns the color (starting from 0) of each vertex.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge("a", "b", 0.0);
+    /// graph.add_oriented_edge("a", "c", 0.0);
+    /// graph.add_oriented_edge("a", "d", 0.0);
+    ///
+    /// let colors = graph.welsh_powell_coloring();
+    /// assert_eq!(colors["a"], 0);
+    /// assert_eq!(colors["b"], 1);
+    /// assert_eq!(colors["d"], 1);
+    /// ```
+    pub fn welsh_powell_coloring(&self) -> BTreeMap<Indent, usize> {
+        let (vertices, neighbours) = self.undirected();
+        let n = vertices.len();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|a, b| neighbours[*b].len().cmp(&neighbours[*a].len()));
+        let mut colors: Vec<Option<usize>> = vec![None; n];
+        let mut colored = 0;
+        let mut color = 0;
+        while colored < n {
+            for vertex in &order {
+                if colors[*vertex].is_none() && neighbours[*vertex].iter().all(|to| colors[*to] != Some(color)) {
+                    colors[*vertex] = Some(color);
+                    colored += 1;
+                }
+            }
+            color += 1;
+        }
+        vertices.into_iter().zip(colors.into_iter().map(|color| color.unwrap())).collect()
+    }
+
+    /// Exact chromatic number (backtracking search, exponential time, intended for small graphs).
+    /// The direction of the edges is ignored.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// for (a, b) in [(1, 2), (2, 3), (3, 4), (4, 5), (5, 1)].iter() {
+    ///     graph.add_oriented_edge(*a, *b, 0.0);
+    /// }
+    /// assert_eq!(graph.chromatic_number(), 3);
+    /// ```
+    pub fn chromatic_number(&self) -> usize {
+        let (_, neighbours) = self.undirected();
+        if neighbours.is_empty() {
+            return 0;
+        }
+        let clique = maximum_clique(&neighbours);
+        let upper = self.dsatur_coloring().values().max().unwrap() + 1;
+        let mut order: Vec<usize> = (0..neighbours.len()).collect();
+        order.sort_by(|a, b| neighbours[*b].len().cmp(&neighbours[*a].len()));
+        for k in clique.len()..upper {
+            let mut colors = vec![None; neighbours.len()];
+            if k_colorable(0, k, &order, &neighbours, &mut colors) {
+                return k;
+            }
+        }
+        upper
+    }
+
+    /// Checks whether the graph is bipartite (the direction of the edges is ignored).
+    /// Returns a two-coloring (colors 0 and 1) as a witness or None if there is an odd cycle.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge(1, 2, 0.0);
+    /// graph.add_oriented_edge(2, 3, 0.0);
+    /// graph.add_oriented_edge(3, 4, 0.0);
+    ///
+    /// let colors = graph.bipartite_coloring().unwrap();
+    /// assert_eq!(colors[&1], colors[&3]);
+    /// assert_ne!(colors[&1], colors[&2]);
+    ///
+    /// graph.add_oriented_edge(3, 1, 0.0);
+    /// assert_eq!(graph.bipartite_coloring(), None);
+    /// ```
+    pub fn bipartite_coloring(&self) -> Option<BTreeMap<Indent, usize>> {
+        let (vertices, neighbours) = self.undirected();
+        let mut colors: Vec<Option<usize>> = vec![None; vertices.len()];
+        for start in 0..vertices.len() {
+            if colors[start].is_some() {
+                continue;
+            }
+            colors[start] = Some(0);
+            let mut queue = VecDeque::new();
+            queue.push_back(start);
+            while let Some(vertex) = queue.pop_front() {
+                let color = colors[vertex].unwrap();
+                for to in &neighbours[vertex] {
+                    match colors[*to] {
+                        None => {
+                            colors[*to] = Some(1 - color);
+                            queue.push_back(*to);
+                        }
+                        Some(value) if value == color => return None,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Some(vertices.into_iter().zip(colors.into_iter().map(|color| color.unwrap())).collect())
+    }
+
+    /// Checks whether the graph is bipartite (the direction of the edges is ignored)
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge(1, 2, 0.0);
+    /// assert!(graph.is_bipartite());
+    /// ```
+    pub fn is_bipartite(&self) -> bool {
+        self.bipartite_coloring().is_some()
+    }
+
+    /// All maximal cliques (Bron–Kerbosch algorithm with pivoting).
+    /// The direction of the edges is ignored, the vertices of each clique are sorted.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge(1, 2, 0.0);
+    /// graph.add_oriented_edge(2, 3, 0.0);
+    /// graph.add_oriented_edge(3, 1, 0.0);
+    /// graph.add_oriented_edge(3, 4, 0.0);
+    ///
+    /// let mut cliques = graph.maximal_cliques();
+    /// cliques.sort();
+    /// assert_eq!(cliques, vec![vec![1, 2, 3], vec![3, 4]]);
+    /// ```
+    pub fn maximal_cliques(&self) -> Vec<Vec<Indent>> {
+        let (vertices, neighbours) = self.undirected();
+        let mut cliques = vec![];
+        if vertices.is_empty() {
+            return cliques;
+        }
+        bron_kerbosch(&neighbours, &mut vec![], (0..vertices.len()).collect(), BTreeSet::new(), &mut |clique| {
+            let mut clique: Vec<Indent> = clique.iter().map(|v| vertices[*v].clone()).collect();
+            clique.sort();
+            cliques.push(clique);
+        });
+        cliques
+    }
+
+    /// Maximum clique (the direction of the edges is ignored)
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge('a', 'b', 0.0);
+    /// graph.add_oriented_edge('b', 'c', 0.0);
+    /// graph.add_oriented_edge('c', 'a', 0.0);
+    /// graph.add_oriented_edge('c', 'd', 0.0);
+    ///
+    /// assert_eq!(graph.maximum_clique(), vec!['a', 'b', 'c']);
+    /// ```
+    pub fn maximum_clique(&self) -> Vec<Indent> {
+        let (vertices, neighbours) = self.undirected();
+        maximum_clique(&neighbours).into_iter().map(|v| vertices[v].clone()).collect()
+    }
+
+    /// Maximum independent set, i.e. the maximum clique of the complement graph
+    /// (the direction of the edges is ignored)
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge(1, 2, 0.0);
+    /// graph.add_oriented_edge(2, 3, 0.0);
+    /// graph.add_oriented_edge(3, 4, 0.0);
+    /// graph.add_oriented_edge(4, 5, 0.0);
+    ///
+    /// assert_eq!(graph.maximum_independent_set(), vec![1, 3, 5]);
+    /// ```
+    pub fn maximum_independent_set(&self) -> Vec<Indent> {
+        let (vertices, neighbours) = self.undirected();
+        let n = vertices.len();
+        let complement: Vec<BTreeSet<usize>> = (0..n)
+            .map(|v| (0..n).filter(|to| *to != v && !neighbours[v].contains(to)).collect())
+            .collect();
+        maximum_clique(&complement).into_iter().map(|v| vertices[v].clone()).collect()
+    }
+}
+
+fn maximum_clique(neighbours: &[BTreeSet<usize>]) -> Vec<usize> {
+    let mut best = vec![];
+    bron_kerbosch(neighbours, &mut vec![], (0..neighbours.len()).collect(), BTreeSet::new(), &mut |clique| {
+        if clique.len() > best.len() {
+            best = clique.to_vec();
+        }
+    });
+    best.sort_unstable();
+    best
+}
+
+fn smallest_free_color(used: &BTreeSet<usize>) -> usize {
+    let mut color = 0;
+    for value in used {
+        if *value != color {
+            break;
+        }
+        color += 1;
+    }
+    color
+}
+
+fn k_colorable(idx: usize, k: usize, order: &[usize], neighbours: &[BTreeSet<usize>], colors: &mut [Option<usize>]) -> bool {
+    if idx == order.len() {
+        return true;
+    }
+    let vertex = order[idx];
+    // colors above the first unused one are symmetric to it
+    let limit = colors.iter().flatten().max().map_or(1, |value| value + 2);
+    for color in 0..min(k, limit) {
+        if neighbours[vertex].iter().all(|to| colors[*to] != Some(color)) {
+            colors[vertex] = Some(color);
+            if k_colorable(idx + 1, k, order, neighbours, colors) {
+                return true;
+            }
+            colors[vertex] = None;
+        }
+    }
+    false
+}
+
+/// Bron–Kerbosch algorithm with pivoting, `report` is called for every maximal clique
+fn bron_kerbosch<F>(neighbours: &[BTreeSet<usize>], clique: &mut Vec<usize>, mut candidates: BTreeSet<usize>, mut excluded: BTreeSet<usize>, report: &mut F)
+    where F: FnMut(&[usize]) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            report(clique);
+        }
+        return;
+    }
+    let pivot = *candidates.union(&excluded)
+        .max_by_key(|v| neighbours[**v].intersection(&candidates).count())
+        .unwrap();
+    let vertices: Vec<usize> = candidates.difference(&neighbours[pivot]).cloned().collect();
+    for vertex in vertices {
+        clique.push(vertex);
+        bron_kerbosch(
+            neighbours,
+            clique,
+            candidates.intersection(&neighbours[vertex]).cloned().collect(),
+            excluded.intersection(&neighbours[vertex]).cloned().collect(),
+            report
+        );
+        clique.pop();
+        candidates.remove(&vertex);
+        excluded.insert(vertex);
+    }
+}
+
+#[test]
+fn test_greedy_coloring() {
+    let mut graph = Graph::new();
+    for (a, b) in &[(1, 2), (2, 3), (3, 4), (4, 1), (1, 5), (5, 6)] {
+        graph.add_oriented_edge(*a, *b, 0.0);
+    }
+    for colors in &[graph.dsatur_coloring(), graph.welsh_powell_coloring()] {
+        assert_eq!(colors.len(), 6);
+        for (a, b) in &[(1, 2), (2, 3), (3, 4), (4, 1), (1, 5), (5, 6)] {
+            assert_ne!(colors[a], colors[b]);
+        }
+    }
+    assert_eq!(graph.dsatur_coloring().values().max(), Some(&1));
+    assert!(Graph::<i32>::new().dsatur_coloring().is_empty());
+}
+
+#[test]
+fn test_chromatic_number() {
+    let mut graph = Graph::new();
+    assert_eq!(graph.chromatic_number(), 0);
+    graph.add_oriented_edge(1, 1, 0.0);
+    assert_eq!(graph.chromatic_number(), 1);
+    graph.add_oriented_edge(1, 2, 0.0);
+    assert_eq!(graph.chromatic_number(), 2);
+
+    // Petersen graph
+    let mut graph = Graph::new();
+    for i in 0..5 {
+        graph.add_oriented_edge(i, (i + 1) % 5, 0.0);
+        graph.add_oriented_edge(i, i + 5, 0.0);
+        graph.add_oriented_edge(i + 5, (i + 2) % 5 + 5, 0.0);
+    }
+    assert_eq!(graph.chromatic_number(), 3);
+
+    let mut graph = Graph::new();
+    for i in 0..5 {
+        for j in 0..i {
+            graph.add_oriented_edge(i, j, 0.0);
+        }
+    }
+    assert_eq!(graph.chromatic_number(), 5);
+}
+
+#[test]
+fn test_bipartite_coloring() {
+    let mut graph = Graph::new();
+    graph.add_oriented_edge(1, 2, 0.0);
+    graph.add_oriented_edge(3, 4, 0.0);
+    graph.add_oriented_edge(4, 5, 0.0);
+    let colors = graph.bipartite_coloring().unwrap();
+    assert_ne!(colors[&1], colors[&2]);
+    assert_ne!(colors[&3], colors[&4]);
+    assert_eq!(colors[&3], colors[&5]);
+    graph.add_oriented_edge(5, 3, 0.0);
+    assert!(!graph.is_bipartite());
+}
+
+#[test]
+fn test_cliques() {
+    let mut graph = Graph::new();
+    for (a, b) in &[(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4), (4, 5), (5, 6), (6, 4)] {
+        graph.add_oriented_edge(*a, *b, 0.0);
+    }
+    let mut cliques = graph.maximal_cliques();
+    cliques.sort();
+    assert_eq!(cliques, vec![vec![1, 2, 3, 4], vec![4, 5, 6]]);
+    assert_eq!(graph.maximum_clique(), vec![1, 2, 3, 4]);
+    assert_eq!(graph.maximum_independent_set().len(), 2);
+    assert!(Graph::<i32>::new().maximal_cliques().is_empty());
+}
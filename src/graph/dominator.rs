@@ -0,0 +1,320 @@
+use std::collections::BTreeSet;
+use super::{Graph, GraphNum};
+
+/// Dominator tree of a flow graph.
+/// A vertex `a` dominates a vertex `b` if every path from the entry to `b` passes through `a`.
+/// Vertices unreachable from the entry are not dominated by anything.
+pub struct DominatorTree<Indent> where Indent: Eq + Ord + Clone {
+    vertices: Vec<Indent>,
+    idom: Vec<Option<usize>>,
+    reachable: Vec<bool>,
+    time_in: Vec<usize>,
+    time_out: Vec<usize>,
+    frontiers: Vec<Vec<usize>>,
+}
+
+impl <Indent> DominatorTree<Indent> where Indent: Eq + Ord + Clone {
+    fn build(vertices: Vec<Indent>, succ: &[Vec<usize>], entry: Option<usize>) -> Self {
+        let n = vertices.len();
+        let mut reachable = vec![false; n];
+        let mut idom = vec![None; n];
+        let mut preds = vec![vec![]; n];
+        if let Some(entry) = entry {
+            idom = lengauer_tarjan(succ, entry, &mut reachable);
+            for (from, edges) in succ.iter().enumerate() {
+                if reachable[from] {
+                    for to in edges {
+                        preds[*to].push(from);
+                    }
+                }
+            }
+        }
+
+        let mut children = vec![vec![]; n];
+        for (v, parent) in idom.iter().enumerate() {
+            if let Some(parent) = parent {
+                children[*parent].push(v);
+            }
+        }
+        let mut time_in = vec![0; n];
+        let mut time_out = vec![0; n];
+        if let Some(entry) = entry {
+            let mut timer = 0;
+            let mut stack = vec![(entry, 0)];
+            time_in[entry] = timer;
+            while let Some((v, idx)) = stack.pop() {
+                if idx < children[v].len() {
+                    stack.push((v, idx + 1));
+                    timer += 1;
+                    time_in[children[v][idx]] = timer;
+                    stack.push((children[v][idx], 0));
+                } else {
+                    timer += 1;
+                    time_out[v] = timer;
+                }
+            }
+        }
+
+        let mut frontiers = vec![BTreeSet::new(); n];
+        for (b, list) in preds.iter().enumerate() {
+            for p in list {
+                let mut runner = Some(*p);
+                while let Some(r) = runner {
+                    if runner == idom[b] {
+                        break;
+                    }
+                    frontiers[r].insert(b);
+                    runner = idom[r];
+                }
+            }
+        }
+        let frontiers = frontiers.into_iter().map(|set| set.into_iter().collect()).collect();
+        DominatorTree { vertices, idom, reachable, time_in, time_out, frontiers }
+    }
+
+    fn position(&self, vertex: &Indent) -> Option<usize> {
+        match self.vertices.binary_search(vertex) {
+            Ok(idx) if self.reachable[idx] => Some(idx),
+            _ => None
+        }
+    }
+
+    /// Returns the immediate dominator of the vertex (None for the entry and unreachable vertices)
+    pub fn immediate_dominator(&self, vertex: &Indent) -> Option<Indent> {
+        self.position(vertex).and_then(|idx| self.idom[idx]).map(|idx| self.vertices[idx].clone())
+    }
+
+    /// Returns all dominators of the vertex starting from the vertex itself up to the entry
+    pub fn dominators(&self, vertex: &Indent) -> Vec<Indent> {
+        let mut res = vec![];
+        let mut current = self.position(vertex);
+        while let Some(idx) = current {
+            res.push(self.vertices[idx].clone());
+            current = self.idom[idx];
+        }
+        res
+    }
+
+    /// Checks whether `a` dominates `b` (every vertex dominates itself)
+    pub fn dominates(&self, a: &Indent, b: &Indent) -> bool {
+        match (self.position(a), self.position(b)) {
+            (Some(a), Some(b)) => self.time_in[a] <= self.time_in[b] && self.time_out[b] <= self.time_out[a],
+            _ => false
+        }
+    }
+
+    /// Returns the dominance frontier of the vertex: the vertices where its dominance ends
+    pub fn dominance_frontier(&self, vertex: &Indent) -> Vec<Indent> {
+        match self.position(vertex) {
+            Some(idx) => self.frontiers[idx].iter().map(|v| self.vertices[*v].clone()).collect(),
+            None => vec![]
+        }
+    }
+}
+
+/// Lengauer-Tarjan algorithm (simple version with path compression).
+/// Returns the immediate dominators, `reachable` is filled for the vertices reachable from the entry.
+fn lengauer_tarjan(succ: &[Vec<usize>], entry: usize, reachable: &mut [bool]) -> Vec<Option<usize>> {
+    let n = succ.len();
+    let mut dfn: Vec<Option<usize>> = vec![None; n];
+    let mut vertex = vec![];
+    let mut parent = vec![];
+    let mut stack = vec![(entry, 0)];
+    dfn[entry] = Some(0);
+    vertex.push(entry);
+    parent.push(0);
+    while let Some((v, idx)) = stack.pop() {
+        if idx < succ[v].len() {
+            stack.push((v, idx + 1));
+            let to = succ[v][idx];
+            if dfn[to].is_none() {
+                dfn[to] = Some(vertex.len());
+                vertex.push(to);
+                parent.push(dfn[v].unwrap());
+                stack.push((to, 0));
+            }
+        }
+    }
+    let m = vertex.len();
+    let mut preds = vec![vec![]; m];
+    for (i, v) in vertex.iter().enumerate() {
+        reachable[*v] = true;
+        for to in &succ[*v] {
+            preds[dfn[*to].unwrap()].push(i);
+        }
+    }
+
+    let mut semi: Vec<usize> = (0..m).collect();
+    let mut label: Vec<usize> = (0..m).collect();
+    let mut ancestor: Vec<Option<usize>> = vec![None; m];
+    let mut idom = vec![0; m];
+    let mut bucket = vec![vec![]; m];
+    for w in (1..m).rev() {
+        for v in &preds[w] {
+            let u = eval(*v, &mut ancestor, &mut label, &semi);
+            if semi[u] < semi[w] {
+                semi[w] = semi[u];
+            }
+        }
+        bucket[semi[w]].push(w);
+        ancestor[w] = Some(parent[w]);
+        for v in std::mem::take(&mut bucket[parent[w]]) {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            idom[v] = if semi[u] < semi[v] { u } else { parent[w] };
+        }
+    }
+    for w in 1..m {
+        if idom[w] != semi[w] {
+            idom[w] = idom[idom[w]];
+        }
+    }
+
+    let mut res = vec![None; n];
+    for w in 1..m {
+        res[vertex[w]] = Some(vertex[idom[w]]);
+    }
+    res
+}
+
+fn eval(v: usize, ancestor: &mut [Option<usize>], label: &mut [usize], semi: &[usize]) -> usize {
+    if ancestor[v].is_none() {
+        return v;
+    }
+    let mut path = vec![];
+    let mut x = v;
+    while let Some(a) = ancestor[x] {
+        if ancestor[a].is_none() {
+            break;
+        }
+        path.push(x);
+        x = a;
+    }
+    while let Some(y) = path.pop() {
+        let a = ancestor[y].unwrap();
+        if semi[label[a]] < semi[label[y]] {
+            label[y] = label[a];
+        }
+        ancestor[y] = ancestor[a];
+    }
+    label[v]
+}
+
+impl <Indent> Graph <Indent> where Indent: Eq + Ord + Clone {
+    /// Dominator tree of the flow graph with the given entry (Lengauer-Tarjan algorithm)
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge("entry", "if", 0.0);
+    /// graph.add_oriented_edge("if", "then", 0.0);
+    /// graph.add_oriented_edge("if", "else", 0.0);
+    /// graph.add_oriented_edge("then", "end", 0.0);
+    /// graph.add_oriented_edge("else", "end", 0.0);
+    ///
+    /// let tree = graph.dominator_tree("entry");
+    /// assert_eq!(tree.immediate_dominator(&"end"), Some("if"));
+    /// assert!(tree.dominates(&"if", &"then"));
+    /// assert!(!tree.dominates(&"then", &"end"));
+    /// assert_eq!(tree.dominance_frontier(&"then"), vec!["end"]);
+    /// ```
+    pub fn dominator_tree(&self, entry: Indent) -> DominatorTree<Indent> {
+        let (vertices, adj) = self.indexed();
+        let succ: Vec<Vec<usize>> = adj.into_iter().map(|edges| edges.into_iter().map(|(to, _)| to).collect()).collect();
+        let entry = vertices.binary_search(&entry).ok();
+        DominatorTree::build(vertices, &succ, entry)
+    }
+}
+
+impl GraphNum {
+    /// Dominator tree of the flow graph with the given entry (Lengauer-Tarjan algorithm)
+    ///```
+    /// use librualg::graph::GraphNum;
+    ///
+    /// let mut graph = GraphNum::new(5);
+    /// for v in 1..6 {
+    ///     graph.add_vertex(v);
+    /// }
+    /// graph.add_oriented_edge(1, 2, 0.0);
+    /// graph.add_oriented_edge(2, 3, 0.0);
+    /// graph.add_oriented_edge(3, 2, 0.0);
+    /// graph.add_oriented_edge(3, 4, 0.0);
+    /// graph.add_oriented_edge(1, 4, 0.0);
+    ///
+    /// let tree = graph.dominator_tree(1);
+    /// assert_eq!(tree.immediate_dominator(&3), Some(2));
+    /// assert_eq!(tree.immediate_dominator(&4), Some(1));
+    /// assert_eq!(tree.immediate_dominator(&5), None);
+    /// assert_eq!(tree.dominance_frontier(&2), vec![2, 4]);
+    /// ```
+    pub fn dominator_tree(&self, entry: usize) -> DominatorTree<usize> {
+        let succ: Vec<Vec<usize>> = self.adj.iter()
+            .map(|edges| edges.as_ref().map_or(vec![], |edges| edges.iter().map(|edge| edge.to).collect()))
+            .collect();
+        let entry = if matches!(self.adj.get(entry), Some(Some(_))) { Some(entry) } else { None };
+        DominatorTree::build((0..self.adj.len()).collect(), &succ, entry)
+    }
+}
+
+#[test]
+fn test_dominator_tree() {
+    // Example from the paper of Lengauer and Tarjan
+    let mut graph = Graph::new();
+    let edges = [
+        ('R', 'A'), ('R', 'B'), ('R', 'C'), ('A', 'D'), ('B', 'A'), ('B', 'D'), ('B', 'E'),
+        ('C', 'F'), ('C', 'G'), ('D', 'L'), ('E', 'H'), ('F', 'I'), ('G', 'I'), ('G', 'J'),
+        ('H', 'E'), ('H', 'K'), ('I', 'K'), ('J', 'I'), ('K', 'I'), ('K', 'R'), ('L', 'H'),
+    ];
+    for (a, b) in edges.iter() {
+        graph.add_oriented_edge(*a, *b, 0.0);
+    }
+    let tree = graph.dominator_tree('R');
+    let expected = [
+        ('R', None), ('A', Some('R')), ('B', Some('R')), ('C', Some('R')), ('D', Some('R')),
+        ('E', Some('R')), ('F', Some('C')), ('G', Some('C')), ('H', Some('R')), ('I', Some('R')),
+        ('J', Some('G')), ('K', Some('R')), ('L', Some('D')),
+    ];
+    for (v, idom) in expected.iter() {
+        assert_eq!(tree.immediate_dominator(v), *idom);
+    }
+    assert!(tree.dominates(&'C', &'J'));
+    assert!(tree.dominates(&'R', &'R'));
+    assert!(!tree.dominates(&'G', &'I'));
+    assert_eq!(tree.dominators(&'J'), vec!['J', 'G', 'C', 'R']);
+    assert_eq!(tree.dominance_frontier(&'G'), vec!['I']);
+    assert_eq!(tree.dominance_frontier(&'C'), vec!['I']);
+    assert_eq!(tree.dominance_frontier(&'E'), vec!['H']);
+    assert_eq!(tree.dominance_frontier(&'K'), vec!['I', 'R']);
+
+    let tree = graph.dominator_tree('Z');
+    assert_eq!(tree.immediate_dominator(&'A'), None);
+    assert!(!tree.dominates(&'R', &'A'));
+}
+
+#[test]
+fn test_dominator_tree_loop() {
+    let mut graph = GraphNum::new(6);
+    for v in 0..6 {
+        graph.add_vertex(v);
+    }
+    graph.add_oriented_edge(0, 1, 0.0);
+    graph.add_oriented_edge(1, 2, 0.0);
+    graph.add_oriented_edge(2, 3, 0.0);
+    graph.add_oriented_edge(3, 1, 0.0);
+    graph.add_oriented_edge(2, 4, 0.0);
+    graph.add_oriented_edge(4, 5, 0.0);
+    graph.add_oriented_edge(3, 5, 0.0);
+    let tree = graph.dominator_tree(0);
+    assert_eq!(tree.immediate_dominator(&0), None);
+    assert_eq!(tree.immediate_dominator(&1), Some(0));
+    assert_eq!(tree.immediate_dominator(&3), Some(2));
+    assert_eq!(tree.immediate_dominator(&5), Some(2));
+    assert_eq!(tree.dominance_frontier(&3), vec![1, 5]);
+    assert_eq!(tree.dominance_frontier(&1), vec![1]);
+    assert!(tree.dominates(&1, &5));
+    assert!(!tree.dominates(&4, &5));
+    assert!(!tree.dominates(&6, &6));
+
+    let tree = graph.dominator_tree(10);
+    assert_eq!(tree.immediate_dominator(&1), None);
+    assert!(!tree.dominates(&0, &1));
+}
@@ -0,0 +1,301 @@
+use rand::{Rng, SeedableRng, StdRng};
+use super::{Graph, GraphNum};
+
+/// Graphs that can be built from a list of oriented edges on the vertices `0..n`
+pub trait FromEdges {
+    fn from_edges(n: usize, edges: &[(usize, usize, f32)]) -> Self;
+}
+
+impl FromEdges for Graph<usize> {
+    fn from_edges(n: usize, edges: &[(usize, usize, f32)]) -> Self {
+        let mut graph = Graph::new();
+        for vertex in 0..n {
+            graph.add_vertex(vertex);
+        }
+        for (from, to, weight) in edges {
+            graph.add_oriented_edge(*from, *to, *weight);
+        }
+        graph
+    }
+}
+
+impl FromEdges for GraphNum {
+    fn from_edges(n: usize, edges: &[(usize, usize, f32)]) -> Self {
+        let mut graph = GraphNum::new(n);
+        for vertex in 0..n {
+            graph.add_vertex(vertex);
+        }
+        for (from, to, weight) in edges {
+            graph.add_oriented_edge(*from, *to, *weight);
+        }
+        graph
+    }
+}
+
+// the seed is split into two 32-bit words, so it is the same on the 32-bit and the 64-bit targets
+fn seeded(seed: u64) -> StdRng {
+    StdRng::from_seed(&[(seed & 0xffff_ffff) as usize, (seed >> 32) as usize])
+}
+
+/// Graph generator with a seedable random number generator.
+/// The vertices of the generated graphs are `0..n`, undirected edges are added in both directions.
+/// The edges get the weight 1.0 or a random weight (see `with_weights`).
+/// The same seed always produces the same graphs.
+///```
+/// use librualg::graph::{Graph, GraphNum, GraphGenerator};
+///
+/// let mut generator = GraphGenerator::new(42);
+/// let graph: Graph<usize> = generator.grid(3, 4);
+/// assert_eq!(graph.search_path(11, &graph.bfs(0)).unwrap().len(), 6);
+///
+/// let graph: GraphNum = GraphGenerator::with_weights(7, 1.0, 10.0).gnp(100, 0.1);
+/// let other: GraphNum = GraphGenerator::with_weights(7, 1.0, 10.0).gnp(100, 0.1);
+/// assert_eq!(graph.dijkstra(0).1, other.dijkstra(0).1);
+/// ```
+pub struct GraphGenerator {
+    rng: StdRng,
+    weights: Option<(f32, f32)>,
+}
+
+impl GraphGenerator {
+    /// Generator of graphs with unit weights
+    pub fn new(seed: u64) -> Self {
+        GraphGenerator { rng: seeded(seed), weights: None }
+    }
+
+    /// Generator of graphs with weights uniformly distributed in `[min, max)`
+    pub fn with_weights(seed: u64, min: f32, max: f32) -> Self {
+        GraphGenerator { rng: seeded(seed), weights: Some((min, max)) }
+    }
+
+    fn weight(&mut self) -> f32 {
+        match self.weights {
+            Some((min, max)) => self.rng.gen_range(min, max),
+            None => 1.0
+        }
+    }
+
+    fn undirected<G: FromEdges>(&mut self, n: usize, pairs: &[(usize, usize)]) -> G {
+        let mut edges = Vec::with_capacity(pairs.len() * 2);
+        for (a, b) in pairs {
+            let weight = self.weight();
+            edges.push((*a, *b, weight));
+            edges.push((*b, *a, weight));
+        }
+        G::from_edges(n, &edges)
+    }
+
+    /// Complete graph on `n` vertices
+    pub fn complete<G: FromEdges>(&mut self, n: usize) -> G {
+        let mut pairs = vec![];
+        for a in 0..n {
+            for b in a + 1..n {
+                pairs.push((a, b));
+            }
+        }
+        self.undirected(n, &pairs)
+    }
+
+    /// Path `0 - 1 - ... - (n - 1)`
+    pub fn path<G: FromEdges>(&mut self, n: usize) -> G {
+        let pairs: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        self.undirected(n, &pairs)
+    }
+
+    /// Cycle `0 - 1 - ... - (n - 1) - 0` (a path for `n < 3`)
+    pub fn cycle<G: FromEdges>(&mut self, n: usize) -> G {
+        let mut pairs: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        if n >= 3 {
+            pairs.push((n - 1, 0));
+        }
+        self.undirected(n, &pairs)
+    }
+
+    /// Star with the center 0 and the leaves `1..n`
+    pub fn star<G: FromEdges>(&mut self, n: usize) -> G {
+        let pairs: Vec<(usize, usize)> = (1..n).map(|v| (0, v)).collect();
+        self.undirected(n, &pairs)
+    }
+
+    /// Grid `rows x cols`, the cell `(r, c)` is the vertex `r * cols + c`
+    pub fn grid<G: FromEdges>(&mut self, rows: usize, cols: usize) -> G {
+        let mut pairs = vec![];
+        for r in 0..rows {
+            for c in 0..cols {
+                if c + 1 < cols {
+                    pairs.push((r * cols + c, r * cols + c + 1));
+                }
+                if r + 1 < rows {
+                    pairs.push((r * cols + c, (r + 1) * cols + c));
+                }
+            }
+        }
+        self.undirected(rows * cols, &pairs)
+    }
+
+    /// Hypercube of the given dimension: `2^dim` vertices, the vertices differing in one bit are adjacent
+    pub fn hypercube<G: FromEdges>(&mut self, dim: usize) -> G {
+        let n = 1 << dim;
+        let mut pairs = vec![];
+        for v in 0..n {
+            for bit in 0..dim {
+                if v & (1 << bit) == 0 {
+                    pairs.push((v, v | (1 << bit)));
+                }
+            }
+        }
+        self.undirected(n, &pairs)
+    }
+
+    /// Erdős–Rényi random graph G(n, p): every pair of vertices is adjacent with probability `p`
+    pub fn gnp<G: FromEdges>(&mut self, n: usize, p: f64) -> G {
+        let mut pairs = vec![];
+        for a in 0..n {
+            for b in a + 1..n {
+                if self.rng.gen::<f64>() < p {
+                    pairs.push((a, b));
+                }
+            }
+        }
+        self.undirected(n, &pairs)
+    }
+
+    /// Barabási–Albert preferential attachment graph.
+    /// Starts from a star on `m + 1` vertices, every new vertex is attached to `m` distinct vertices
+    /// chosen with probability proportional to their degree. None unless `1 <= m < n`.
+    pub fn barabasi_albert<G: FromEdges>(&mut self, n: usize, m: usize) -> Option<G> {
+        if m == 0 || m >= n {
+            return None;
+        }
+        let mut pairs: Vec<(usize, usize)> = (1..=m).map(|v| (0, v)).collect();
+        let mut repeated: Vec<usize> = pairs.iter().flat_map(|(a, b)| vec![*a, *b]).collect();
+        for source in m + 1..n {
+            let mut targets = vec![];
+            while targets.len() < m {
+                let target = repeated[self.rng.gen_range(0, repeated.len())];
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+            for target in targets {
+                pairs.push((target, source));
+                repeated.push(target);
+                repeated.push(source);
+            }
+        }
+        Some(self.undirected(n, &pairs))
+    }
+
+    /// Uniformly distributed random labeled tree (built from a random Prüfer sequence)
+    pub fn random_tree<G: FromEdges>(&mut self, n: usize) -> G {
+        if n < 2 {
+            return self.undirected(n, &[]);
+        }
+        let prufer: Vec<usize> = (0..n - 2).map(|_| self.rng.gen_range(0, n)).collect();
+        let mut degree = vec![1; n];
+        for v in &prufer {
+            degree[*v] += 1;
+        }
+        let mut pairs = Vec::with_capacity(n - 1);
+        let mut ptr = 0;
+        while degree[ptr] != 1 {
+            ptr += 1;
+        }
+        let mut leaf = ptr;
+        for v in prufer {
+            pairs.push((leaf, v));
+            degree[v] -= 1;
+            if v < ptr && degree[v] == 1 {
+                leaf = v;
+            } else {
+                ptr += 1;
+                while degree[ptr] != 1 {
+                    ptr += 1;
+                }
+                leaf = ptr;
+            }
+        }
+        pairs.push((leaf, n - 1));
+        self.undirected(n, &pairs)
+    }
+
+    /// Random DAG: every oriented edge `a -> b` with `a < b` is present with probability `p`,
+    /// so `0..n` is a topological order
+    pub fn random_dag<G: FromEdges>(&mut self, n: usize, p: f64) -> G {
+        let mut edges = vec![];
+        for a in 0..n {
+            for b in a + 1..n {
+                if self.rng.gen::<f64>() < p {
+                    let weight = self.weight();
+                    edges.push((a, b, weight));
+                }
+            }
+        }
+        G::from_edges(n, &edges)
+    }
+}
+
+#[test]
+fn test_deterministic_generators() {
+    let mut generator = GraphGenerator::new(0);
+
+    let graph: Graph<usize> = generator.complete(5);
+    assert!(graph.degree_centrality().values().all(|value| *value == 1.0));
+    assert_eq!(graph.chromatic_number(), 5);
+
+    let graph: Graph<usize> = generator.path(5);
+    assert_eq!(graph.search_path(4, &graph.bfs(0)).unwrap(), vec![0, 1, 2, 3, 4]);
+
+    let graph: Graph<usize> = generator.cycle(5);
+    assert_eq!(graph.search_path(4, &graph.bfs(0)).unwrap(), vec![0, 4]);
+    assert_eq!(graph.chromatic_number(), 3);
+
+    let graph: Graph<usize> = generator.star(6);
+    assert_eq!(graph.degree_centrality()[&0], 1.0);
+    assert_eq!(graph.degree_centrality()[&3], 0.2);
+
+    let graph: Graph<usize> = generator.grid(2, 3);
+    assert_eq!(graph.search_path(5, &graph.bfs(0)).unwrap().len(), 4);
+    assert!(graph.is_bipartite());
+
+    let graph: Graph<usize> = generator.hypercube(3);
+    assert!(graph.is_bipartite());
+    assert!(graph.degree_centrality().values().all(|value| *value == 3.0 / 7.0));
+
+    let graph: Graph<usize> = generator.path(1);
+    assert_eq!(graph.connected_components(), vec![vec![0]]);
+}
+
+#[test]
+fn test_random_generators() {
+    let first: GraphNum = GraphGenerator::with_weights(1, 0.5, 5.0).gnp(50, 0.2);
+    let second: GraphNum = GraphGenerator::with_weights(1, 0.5, 5.0).gnp(50, 0.2);
+    assert_eq!(first.dijkstra(0).1, second.dijkstra(0).1);
+    let third: GraphNum = GraphGenerator::with_weights(2, 0.5, 5.0).gnp(50, 0.2);
+    assert_ne!(first.dijkstra(0).1, third.dijkstra(0).1);
+    // the high half of the seed is used
+    let fourth: GraphNum = GraphGenerator::with_weights(1 + (1 << 32), 0.5, 5.0).gnp(50, 0.2);
+    assert_ne!(first.dijkstra(0).1, fourth.dijkstra(0).1);
+
+    let mut generator = GraphGenerator::new(5);
+    for n in 1..30 {
+        let tree: Graph<usize> = generator.random_tree(n);
+        assert_eq!(tree.connected_components().len(), 1);
+        let edges: f64 = tree.degree_centrality().values().sum::<f64>() * (n as f64 - 1.0);
+        assert_eq!(edges.round() as usize, if n > 1 { 2 * (n - 1) } else { 0 });
+    }
+
+    let graph: Graph<usize> = generator.barabasi_albert(100, 3).unwrap();
+    assert_eq!(graph.connected_components().len(), 1);
+    let edges: f64 = graph.degree_centrality().values().sum::<f64>() * 99.0;
+    assert_eq!(edges.round() as usize, 2 * (3 + 96 * 3));
+    assert!(generator.barabasi_albert::<Graph<usize>>(3, 3).is_none());
+    assert!(generator.barabasi_albert::<Graph<usize>>(3, 0).is_none());
+    assert!(generator.barabasi_albert::<Graph<usize>>(2, 1).is_some());
+
+    let dag: Graph<usize> = generator.random_dag(30, 0.3);
+    assert_eq!(dag.adj.len(), 30);
+    assert!(dag.adj.iter().all(|(from, edges)| edges.iter().all(|edge| *from < edge.to)));
+    let dag: GraphNum = generator.random_dag(30, 0.3);
+    assert!(dag.adj.iter().enumerate().all(|(from, edges)| edges.iter().flatten().all(|edge| from < edge.to)));
+}
@@ -0,0 +1,527 @@
+use std::collections::BTreeMap;
+use super::Graph;
+
+/// Simple directed graph on the vertices `0..n` (parallel edges are merged, the first weight is kept)
+struct Adjacency {
+    succ: Vec<Vec<usize>>,
+    pred: Vec<Vec<usize>>,
+    weights: BTreeMap<(usize, usize), f32>,
+}
+
+impl Adjacency {
+    fn new(adj: &[Vec<(usize, f32)>]) -> Self {
+        let mut weights = BTreeMap::new();
+        for (from, edges) in adj.iter().enumerate() {
+            for (to, weight) in edges {
+                weights.entry((from, *to)).or_insert(*weight);
+            }
+        }
+        let mut succ = vec![vec![]; adj.len()];
+        let mut pred = vec![vec![]; adj.len()];
+        for (from, to) in weights.keys() {
+            succ[*from].push(*to);
+            pred[*to].push(*from);
+        }
+        Adjacency { succ, pred, weights }
+    }
+
+    fn edge(&self, from: usize, to: usize) -> Option<f32> {
+        self.weights.get(&(from, to)).cloned()
+    }
+
+    fn degree_sequence(&self) -> Vec<(usize, usize, bool)> {
+        let mut res: Vec<(usize, usize, bool)> = (0..self.succ.len())
+            .map(|v| (self.pred[v].len(), self.succ[v].len(), self.edge(v, v).is_some()))
+            .collect();
+        res.sort_unstable();
+        res
+    }
+}
+
+/// Weisfeiler-Lehman color refinement performed on both graphs with a common palette.
+/// Returns None if the color histograms differ (the graphs are not isomorphic).
+fn weisfeiler_lehman(g1: &Adjacency, g2: &Adjacency) -> Option<(Vec<usize>, Vec<usize>)> {
+    fn refine(g: &Adjacency, colors: Option<&[usize]>, palette: &mut BTreeMap<Vec<usize>, usize>) -> Vec<usize> {
+        (0..g.succ.len()).map(|v| {
+            let key = match colors {
+                None => vec![g.pred[v].len(), g.succ[v].len(), g.edge(v, v).is_some() as usize],
+                Some(colors) => {
+                    let mut pred: Vec<usize> = g.pred[v].iter().map(|u| colors[*u]).collect();
+                    let mut succ: Vec<usize> = g.succ[v].iter().map(|u| colors[*u]).collect();
+                    pred.sort_unstable();
+                    succ.sort_unstable();
+                    let mut key = vec![colors[v], pred.len()];
+                    key.extend(pred);
+                    key.extend(succ);
+                    key
+                }
+            };
+            let next = palette.len();
+            *palette.entry(key).or_insert(next)
+        }).collect()
+    }
+
+    fn histogram(colors: &[usize]) -> Vec<usize> {
+        let mut res = colors.to_vec();
+        res.sort_unstable();
+        res
+    }
+
+    let mut palette = BTreeMap::new();
+    let mut c1 = refine(g1, None, &mut palette);
+    let mut c2 = refine(g2, None, &mut palette);
+    let mut distinct = palette.len();
+    loop {
+        if histogram(&c1) != histogram(&c2) {
+            return None;
+        }
+        // refinement only splits the color classes, the same number of colors means a stable coloring
+        let mut palette = BTreeMap::new();
+        let next1 = refine(g1, Some(&c1), &mut palette);
+        let next2 = refine(g2, Some(&c2), &mut palette);
+        if palette.len() == distinct {
+            return Some((c1, c2));
+        }
+        distinct = palette.len();
+        c1 = next1;
+        c2 = next2;
+    }
+}
+
+type NodeMatch<'a, Indent, Other> = Box<dyn Fn(&Indent, &Other) -> bool + 'a>;
+type EdgeMatch<'a> = Box<dyn Fn(f32, f32) -> bool + 'a>;
+
+struct Frame {
+    pattern_vertex: usize,
+    candidates: Vec<usize>,
+    pos: usize,
+    applied: Option<usize>,
+}
+
+/// Iterator over the (induced) subgraph isomorphisms found by VF2 algorithm.
+/// Every item maps the vertices of the pattern to the vertices of the graph.
+pub struct SubgraphIsomorphisms<'a, Indent, Other> where Indent: Eq + Ord + Clone, Other: Eq + Ord + Clone {
+    g1: Adjacency,
+    g2: Adjacency,
+    v1: Vec<Indent>,
+    v2: Vec<Other>,
+    node_match: NodeMatch<'a, Indent, Other>,
+    edge_match: EdgeMatch<'a>,
+    colors: Option<(Vec<usize>, Vec<usize>)>,
+    exact: bool,
+    core1: Vec<Option<usize>>,
+    core2: Vec<Option<usize>>,
+    in1: Vec<usize>,
+    out1: Vec<usize>,
+    in2: Vec<usize>,
+    out2: Vec<usize>,
+    stack: Vec<Frame>,
+    started: bool,
+    done: bool,
+}
+
+impl <'a, Indent, Other> SubgraphIsomorphisms<'a, Indent, Other> where Indent: Eq + Ord + Clone, Other: Eq + Ord + Clone {
+    fn new(graph: &Graph<Indent>, pattern: &Graph<Other>, exact: bool,
+           node_match: NodeMatch<'a, Indent, Other>, edge_match: EdgeMatch<'a>) -> Self {
+        let (v1, adj1) = graph.indexed();
+        let (v2, adj2) = pattern.indexed();
+        let g1 = Adjacency::new(&adj1);
+        let g2 = Adjacency::new(&adj2);
+        let mut done = v2.len() > v1.len() || g2.weights.len() > g1.weights.len();
+        let mut colors = None;
+        if exact && !done {
+            done = v1.len() != v2.len() || g1.weights.len() != g2.weights.len() || g1.degree_sequence() != g2.degree_sequence();
+            if !done {
+                colors = weisfeiler_lehman(&g1, &g2);
+                done = colors.is_none();
+            }
+        }
+        let (n1, n2) = (v1.len(), v2.len());
+        SubgraphIsomorphisms {
+            g1, g2, v1, v2, node_match, edge_match, colors, exact,
+            core1: vec![None; n1], core2: vec![None; n2],
+            in1: vec![0; n1], out1: vec![0; n1], in2: vec![0; n2], out2: vec![0; n2],
+            stack: vec![], started: false, done
+        }
+    }
+
+    fn frame(&self) -> Frame {
+        let unmatched1 = |set: &[usize]| -> Vec<usize> {
+            (0..self.v1.len()).filter(|v| self.core1[*v].is_none() && set[*v] != 0).collect()
+        };
+        let first2 = |set: &[usize]| (0..self.v2.len()).find(|v| self.core2[*v].is_none() && set[*v] != 0);
+        if let Some(m) = first2(&self.out2) {
+            return Frame { pattern_vertex: m, candidates: unmatched1(&self.out1), pos: 0, applied: None };
+        }
+        if let Some(m) = first2(&self.in2) {
+            return Frame { pattern_vertex: m, candidates: unmatched1(&self.in1), pos: 0, applied: None };
+        }
+        let m = (0..self.v2.len()).find(|v| self.core2[*v].is_none()).unwrap();
+        let candidates = (0..self.v1.len()).filter(|v| self.core1[*v].is_none()).collect();
+        Frame { pattern_vertex: m, candidates, pos: 0, applied: None }
+    }
+
+    fn compare(&self, a: usize, b: usize) -> bool {
+        if self.exact { a == b } else { a >= b }
+    }
+
+    fn lookahead(&self, list1: &[usize], list2: &[usize]) -> bool {
+        let count = |list: &[usize], core: &[Option<usize>], tin: &[usize], tout: &[usize]| {
+            let mut res = (0, 0, 0);
+            for v in list {
+                if core[*v].is_none() {
+                    if tin[*v] != 0 {
+                        res.0 += 1;
+                    }
+                    if tout[*v] != 0 {
+                        res.1 += 1;
+                    }
+                    if tin[*v] == 0 && tout[*v] == 0 {
+                        res.2 += 1;
+                    }
+                }
+            }
+            res
+        };
+        let a = count(list1, &self.core1, &self.in1, &self.out1);
+        let b = count(list2, &self.core2, &self.in2, &self.out2);
+        self.compare(a.0, b.0) && self.compare(a.1, b.1) && self.compare(a.2, b.2)
+    }
+
+    fn feasible(&self, n: usize, m: usize) -> bool {
+        if let Some((c1, c2)) = &self.colors {
+            if c1[n] != c2[m] {
+                return false;
+            }
+        }
+        if !self.compare(self.g1.succ[n].len(), self.g2.succ[m].len()) || !self.compare(self.g1.pred[n].len(), self.g2.pred[m].len()) {
+            return false;
+        }
+        if !(self.node_match)(&self.v1[n], &self.v2[m]) {
+            return false;
+        }
+        match (self.g1.edge(n, n), self.g2.edge(m, m)) {
+            (Some(a), Some(b)) if !(self.edge_match)(a, b) => return false,
+            (Some(_), None) | (None, Some(_)) => return false,
+            _ => {}
+        }
+        for to in &self.g2.succ[m] {
+            if let Some(image) = self.core2[*to] {
+                match self.g1.edge(n, image) {
+                    Some(weight) if (self.edge_match)(weight, self.g2.edge(m, *to).unwrap()) => {}
+                    _ => return false
+                }
+            }
+        }
+        for from in &self.g2.pred[m] {
+            if let Some(image) = self.core2[*from] {
+                match self.g1.edge(image, n) {
+                    Some(weight) if (self.edge_match)(weight, self.g2.edge(*from, m).unwrap()) => {}
+                    _ => return false
+                }
+            }
+        }
+        for to in &self.g1.succ[n] {
+            if let Some(image) = self.core1[*to] {
+                if self.g2.edge(m, image).is_none() {
+                    return false;
+                }
+            }
+        }
+        for from in &self.g1.pred[n] {
+            if let Some(image) = self.core1[*from] {
+                if self.g2.edge(image, m).is_none() {
+                    return false;
+                }
+            }
+        }
+        self.lookahead(&self.g1.succ[n], &self.g2.succ[m]) && self.lookahead(&self.g1.pred[n], &self.g2.pred[m])
+    }
+
+    fn apply(&mut self, n: usize, m: usize, depth: usize) {
+        self.core1[n] = Some(m);
+        self.core2[m] = Some(n);
+        fn mark(g: &Adjacency, v: usize, depth: usize, tin: &mut [usize], tout: &mut [usize]) {
+            if tin[v] == 0 {
+                tin[v] = depth;
+            }
+            if tout[v] == 0 {
+                tout[v] = depth;
+            }
+            for from in &g.pred[v] {
+                if tin[*from] == 0 {
+                    tin[*from] = depth;
+                }
+            }
+            for to in &g.succ[v] {
+                if tout[*to] == 0 {
+                    tout[*to] = depth;
+                }
+            }
+        }
+        mark(&self.g1, n, depth, &mut self.in1, &mut self.out1);
+        mark(&self.g2, m, depth, &mut self.in2, &mut self.out2);
+    }
+
+    fn undo(&mut self, n: usize, m: usize, depth: usize) {
+        fn clear(set: &mut [usize], depth: usize) {
+            for value in set.iter_mut() {
+                if *value == depth {
+                    *value = 0;
+                }
+            }
+        }
+        self.core1[n] = None;
+        self.core2[m] = None;
+        clear(&mut self.in1, depth);
+        clear(&mut self.out1, depth);
+        clear(&mut self.in2, depth);
+        clear(&mut self.out2, depth);
+    }
+
+    fn mapping(&self) -> BTreeMap<Other, Indent> {
+        self.core2.iter().enumerate().map(|(m, n)| (self.v2[m].clone(), self.v1[n.unwrap()].clone())).collect()
+    }
+}
+
+impl <'a, Indent, Other> Iterator for SubgraphIsomorphisms<'a, Indent, Other> where Indent: Eq + Ord + Clone, Other: Eq + Ord + Clone {
+    type Item = BTreeMap<Other, Indent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            if self.v2.is_empty() {
+                self.done = true;
+                return Some(BTreeMap::new());
+            }
+            let frame = self.frame();
+            self.stack.push(frame);
+        }
+        while let Some(mut frame) = self.stack.pop() {
+            // the depth starts from 1, 0 marks the vertices outside the terminal sets
+            let depth = self.stack.len() + 1;
+            let m = frame.pattern_vertex;
+            if let Some(n) = frame.applied.take() {
+                self.undo(n, m, depth);
+            }
+            while frame.pos < frame.candidates.len() {
+                let n = frame.candidates[frame.pos];
+                frame.pos += 1;
+                if self.feasible(n, m) {
+                    self.apply(n, m, depth);
+                    frame.applied = Some(n);
+                    break;
+                }
+            }
+            if frame.applied.is_none() {
+                continue;
+            }
+            self.stack.push(frame);
+            if self.stack.len() == self.v2.len() {
+                return Some(self.mapping());
+            }
+            let next = self.frame();
+            self.stack.push(next);
+        }
+        self.done = true;
+        None
+    }
+}
+
+impl <Indent> Graph <Indent> where Indent: Eq + Ord + Clone {
+    /// Checks whether two graphs are isomorphic (VF2 algorithm).
+    /// The graphs are compared as simple directed graphs, the weights are ignored.
+    /// Degree sequences and Weisfeiler-Lehman colors are compared first to reject most non-isomorphic pairs quickly.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut first = Graph::new();
+    /// first.add_oriented_edge(1, 2, 0.0);
+    /// first.add_oriented_edge(2, 3, 0.0);
+    /// first.add_oriented_edge(3, 1, 0.0);
+    ///
+    /// let mut second = Graph::new();
+    /// second.add_oriented_edge("a", "c", 0.0);
+    /// second.add_oriented_edge("c", "b", 0.0);
+    /// second.add_oriented_edge("b", "a", 0.0);
+    /// assert!(first.is_isomorphic(&second));
+    ///
+    /// second.add_oriented_edge("a", "b", 0.0);
+    /// assert!(!first.is_isomorphic(&second));
+    /// ```
+    pub fn is_isomorphic<Other>(&self, other: &Graph<Other>) -> bool where Other: Eq + Ord + Clone {
+        self.isomorphism(other).is_some()
+    }
+
+    /// Returns an isomorphism between two graphs (a map from the vertices of `other` to the vertices of `self`)
+    /// or None if the graphs are not isomorphic
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut first = Graph::new();
+    /// first.add_oriented_edge(1, 2, 0.0);
+    /// first.add_oriented_edge(2, 3, 0.0);
+    ///
+    /// let mut second = Graph::new();
+    /// second.add_oriented_edge('b', 'c', 0.0);
+    /// second.add_oriented_edge('a', 'b', 0.0);
+    ///
+    /// let mapping = first.isomorphism(&second).unwrap();
+    /// assert_eq!(mapping[&'a'], 1);
+    /// assert_eq!(mapping[&'c'], 3);
+    /// ```
+    pub fn isomorphism<Other>(&self, other: &Graph<Other>) -> Option<BTreeMap<Other, Indent>> where Other: Eq + Ord + Clone {
+        SubgraphIsomorphisms::new(self, other, true, Box::new(|_, _| true), Box::new(|_, _| true)).next()
+    }
+
+    /// Checks whether two graphs are isomorphic taking into account the vertex predicate
+    /// `node_match(vertex of self, vertex of other)` and the edge predicate `edge_match(weight in self, weight in other)`
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut first = Graph::new();
+    /// first.add_oriented_edge("C1", "O1", 2.0);
+    /// first.add_oriented_edge("O1", "C1", 2.0);
+    ///
+    /// let mut second = Graph::new();
+    /// second.add_oriented_edge("C7", "O3", 1.0);
+    /// second.add_oriented_edge("O3", "C7", 1.0);
+    ///
+    /// let same_element = |a: &&str, b: &&str| a[..1] == b[..1];
+    /// assert!(first.is_isomorphic_matching(&second, same_element, |_, _| true));
+    /// assert!(!first.is_isomorphic_matching(&second, same_element, |a, b| a == b));
+    /// ```
+    pub fn is_isomorphic_matching<Other, N, E>(&self, other: &Graph<Other>, node_match: N, edge_match: E) -> bool
+        where Other: Eq + Ord + Clone, N: Fn(&Indent, &Other) -> bool, E: Fn(f32, f32) -> bool {
+        SubgraphIsomorphisms::new(self, other, true, Box::new(node_match), Box::new(edge_match)).next().is_some()
+    }
+
+    /// Iterator over all induced subgraphs of the graph isomorphic to the pattern (VF2 algorithm).
+    /// Every item maps the vertices of the pattern to the vertices of the graph.
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge(1, 2, 0.0);
+    /// graph.add_oriented_edge(2, 3, 0.0);
+    /// graph.add_oriented_edge(3, 4, 0.0);
+    ///
+    /// let mut pattern = Graph::new();
+    /// pattern.add_oriented_edge('a', 'b', 0.0);
+    /// pattern.add_oriented_edge('b', 'c', 0.0);
+    ///
+    /// let matches: Vec<_> = graph.subgraph_isomorphisms(&pattern).collect();
+    /// assert_eq!(matches.len(), 2);
+    /// assert_eq!(matches[0][&'a'], 1);
+    /// assert_eq!(matches[1][&'a'], 2);
+    /// ```
+    pub fn subgraph_isomorphisms<'a, Other>(&self, pattern: &Graph<Other>) -> SubgraphIsomorphisms<'a, Indent, Other> where Other: Eq + Ord + Clone {
+        SubgraphIsomorphisms::new(self, pattern, false, Box::new(|_, _| true), Box::new(|_, _| true))
+    }
+
+    /// Iterator over all induced subgraphs of the graph isomorphic to the pattern taking into account
+    /// the vertex predicate `node_match(vertex of self, vertex of pattern)`
+    /// and the edge predicate `edge_match(weight in self, weight in pattern)`
+    ///```
+    /// use librualg::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_oriented_edge("app", "serde", 1.0);
+    /// graph.add_oriented_edge("app", "rand", 2.0);
+    ///
+    /// let mut pattern = Graph::new();
+    /// pattern.add_oriented_edge(0, 1, 2.0);
+    ///
+    /// let matches: Vec<_> = graph.subgraph_isomorphisms_matching(&pattern, |_, _| true, |a, b| a == b).collect();
+    /// assert_eq!(matches.len(), 1);
+    /// assert_eq!(matches[0][&1], "rand");
+    /// ```
+    pub fn subgraph_isomorphisms_matching<'a, Other, N, E>(&self, pattern: &Graph<Other>, node_match: N, edge_match: E) -> SubgraphIsomorphisms<'a, Indent, Other>
+        where Other: Eq + Ord + Clone, N: Fn(&Indent, &Other) -> bool + 'a, E: Fn(f32, f32) -> bool + 'a {
+        SubgraphIsomorphisms::new(self, pattern, false, Box::new(node_match), Box::new(edge_match))
+    }
+}
+
+#[test]
+fn test_is_isomorphic() {
+    // Petersen graph in two different labelings
+    let mut first = Graph::new();
+    for i in 0..5 {
+        for (a, b) in &[(i, (i + 1) % 5), (i, i + 5), (i + 5, (i + 2) % 5 + 5)] {
+            first.add_oriented_edge(*a, *b, 0.0);
+            first.add_oriented_edge(*b, *a, 0.0);
+        }
+    }
+    let relabel = [3, 7, 1, 9, 0, 5, 2, 8, 6, 4];
+    let mut second = Graph::new();
+    for i in 0..5 {
+        for (a, b) in &[(i, (i + 1) % 5), (i, i + 5), (i + 5, (i + 2) % 5 + 5)] {
+            second.add_oriented_edge(relabel[*a], relabel[*b], 0.0);
+            second.add_oriented_edge(relabel[*b], relabel[*a], 0.0);
+        }
+    }
+    let mapping = first.isomorphism(&second).unwrap();
+    for (a, edges) in &second.adj {
+        for edge in edges {
+            assert!(first.adj[&mapping[a]].iter().any(|e| e.to == mapping[&edge.to]));
+        }
+    }
+
+    // 3-regular graphs with the same degree sequence: the prism is not isomorphic to K(3, 3)
+    let mut prism = Graph::new();
+    let mut k33 = Graph::new();
+    for (a, b) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (0, 3), (1, 4), (2, 5)] {
+        prism.add_oriented_edge(*a, *b, 0.0);
+        prism.add_oriented_edge(*b, *a, 0.0);
+    }
+    for a in 0..3 {
+        for b in 3..6 {
+            k33.add_oriented_edge(a, b, 0.0);
+            k33.add_oriented_edge(b, a, 0.0);
+        }
+    }
+    assert!(!prism.is_isomorphic(&k33));
+    assert!(prism.is_isomorphic(&prism));
+
+    let mut loop_graph = Graph::new();
+    loop_graph.add_oriented_edge(1, 1, 0.0);
+    let mut single = Graph::new();
+    single.add_vertex(1);
+    assert!(!loop_graph.is_isomorphic(&single));
+    assert!(Graph::<u8>::new().is_isomorphic(&Graph::<u8>::new()));
+}
+
+#[test]
+fn test_subgraph_isomorphisms() {
+    let mut graph = Graph::new();
+    for (a, b) in &[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 3)] {
+        graph.add_oriented_edge(*a, *b, 0.0);
+    }
+    let mut triangle = Graph::new();
+    triangle.add_oriented_edge(0, 1, 0.0);
+    triangle.add_oriented_edge(1, 2, 0.0);
+    triangle.add_oriented_edge(2, 0, 0.0);
+    assert_eq!(graph.subgraph_isomorphisms(&triangle).count(), 6);
+
+    // induced: a path of two edges does not match inside a triangle
+    let mut path = Graph::new();
+    path.add_oriented_edge(0, 1, 0.0);
+    path.add_oriented_edge(1, 2, 0.0);
+    let matches: Vec<_> = graph.subgraph_isomorphisms(&path).collect();
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0][&1], 3);
+    assert_eq!(matches[1][&1], 3);
+
+    let matches: Vec<_> = graph.subgraph_isomorphisms_matching(&triangle, |a, _| *a != 1, |_, _| true).collect();
+    assert_eq!(matches.len(), 3);
+
+    let mut big = Graph::new();
+    big.add_oriented_edge(0, 1, 0.0);
+    big.add_oriented_edge(1, 2, 0.0);
+    big.add_oriented_edge(2, 3, 0.0);
+    assert_eq!(path.subgraph_isomorphisms(&big).count(), 0);
+    assert_eq!(graph.subgraph_isomorphisms(&Graph::<u8>::new()).count(), 1);
+}
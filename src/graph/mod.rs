@@ -3,6 +3,16 @@ use std::option::Option::Some;
 use std::cmp::{Ordering};
 use crate::dsu::{DSU, DSUNum};
 
+mod centrality;
+mod coloring;
+mod dominator;
+mod generators;
+mod isomorphism;
+
+pub use dominator::DominatorTree;
+pub use generators::{GraphGenerator, FromEdges};
+pub use isomorphism::SubgraphIsomorphisms;
+
 #[derive(Copy, Clone, PartialOrd, PartialEq)]
 enum Color {
     White = 0,
@@ -47,6 +57,27 @@ impl <Indent> Graph <Indent> where Indent: Eq + Ord + Clone {
         Graph::default()
     }
 
+    /// Returns all vertices in ascending order (including vertices without outgoing edges)
+    /// and the adjacency lists expressed through positions in that order
+    fn indexed(&self) -> (Vec<Indent>, Vec<Vec<(usize, f32)>>) {
+        let mut vertices = BTreeSet::new();
+        for (from, edges) in &self.adj {
+            vertices.insert(from);
+            for edge in edges {
+                vertices.insert(&edge.to);
+            }
+        }
+        let vertices: Vec<Indent> = vertices.into_iter().cloned().collect();
+        let mut adj = vec![vec![]; vertices.len()];
+        for (from, edges) in &self.adj {
+            let idx = vertices.binary_search(from).unwrap();
+            for edge in edges {
+                adj[idx].push((vertices.binary_search(&edge.to).unwrap(), edge.weight));
+            }
+        }
+        (vertices, adj)
+    }
+
     /// BFS (Breadth-First Search) algorithm.
     /// Returns an ancestor vector along the graph traversal path
     ///```
@@ -402,6 +433,11 @@ impl <Indent> Graph <Indent> where Indent: Eq + Ord + Clone {
         graph
     }
 
+    /// Adds a new vertex to the graph (if it does not exist yet)
+    pub fn add_vertex(&mut self, vertex: Indent) {
+        self.adj.entry(vertex).or_default();
+    }
+
     /// Adds a new oriented edge to the graph
     pub fn add_oriented_edge(&mut self, from: Indent, to: Indent, weight: f32) {
         match self.adj.get_mut(&from) {
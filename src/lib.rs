@@ -6,6 +6,8 @@
 pub mod binary_search;
 /// String-searching algorithms.
 pub mod string;
+/// Diff of sequences and texts
+pub mod diff;
 /// Combinatorics algorithms
 pub mod combinatorics;
 /// Graph algorithms
@@ -18,6 +20,8 @@ pub mod math;
 pub mod trie;
 /// Huffman Coding
 pub mod huffman;
+/// LZ77, LZ78 and LZW compression
+pub mod lz;
 /// Bloom filter
 pub mod bloom_filter;
 /// Sparse table
@@ -0,0 +1,408 @@
+use std::collections::{BTreeMap, HashMap};
+
+// longest walk along the chain of the previous positions with the same hash
+const MAX_CHAIN: usize = 256;
+// the shortest match worth a reference
+const MIN_MATCH: usize = 3;
+// the longest match of a triple, it bounds the output of a triple in `decode`
+const MAX_MATCH: usize = 258;
+
+fn write_varint(out: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Option<usize> {
+    let mut res = 0usize;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes.get(*pos)?;
+        *pos += 1;
+        if shift >= usize::MAX.count_ones() {
+            return None;
+        }
+        res |= ((byte & 0x7f) as usize).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(res);
+        }
+        shift += 7;
+    }
+}
+
+/// LZ77 compression with a sliding window.
+/// The data is encoded as the triples (offset back, length of the match, next byte),
+/// a byte without a match is stored as (0, byte), the matches are found by the hash chains of the 3-byte prefixes.
+/// A match is at most 258 bytes long. The chains are keyed by the prefixes themselves rather than
+/// by `RollingHash` or `z_function`: the key of 3 bytes has no collisions and is cheaper than a hash.
+///```
+/// use librualg::lz::Lz77;
+///
+/// let data = b"abracadabra abracadabra abracadabra";
+/// let lz = Lz77::new(4096);
+/// let encoded = lz.encode(data);
+/// assert!(encoded.len() < data.len());
+/// assert_eq!(Lz77::decode(&encoded), Some(data.to_vec()));
+/// ```
+pub struct Lz77 {
+    window: usize,
+}
+
+impl Lz77 {
+    /// `window` is the longest distance back to a match
+    pub fn new(window: usize) -> Self {
+        Lz77 { window }
+    }
+
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let n = data.len();
+        let mut res = vec![];
+        write_varint(&mut res, n);
+        let mut head: HashMap<[u8; 3], usize> = HashMap::new();
+        // prev[idx % size]: the previous position with the prefix of idx, only the window is kept
+        let size = self.window.min(n) + 1;
+        let mut prev = vec![usize::MAX; size];
+        let mut pos = 0;
+        while pos < n {
+            let (mut best_offset, mut best_len) = (0, 0);
+            if pos + MIN_MATCH <= n {
+                let key = [data[pos], data[pos + 1], data[pos + 2]];
+                let mut candidate = head.get(&key).cloned();
+                let mut steps = 0;
+                while let Some(start) = candidate {
+                    if pos - start > self.window || steps == MAX_CHAIN {
+                        break;
+                    }
+                    // the match can overlap the current position, the decoder copies byte by byte
+                    // (the last byte is left for the triple)
+                    let mut len = 0;
+                    while len < MAX_MATCH && pos + len + 1 < n && data[start + len] == data[pos + len] {
+                        len += 1;
+                    }
+                    if len > best_len {
+                        best_len = len;
+                        best_offset = pos - start;
+                    }
+                    candidate = if prev[start % size] == usize::MAX { None } else { Some(prev[start % size]) };
+                    steps += 1;
+                }
+            }
+            if best_len < MIN_MATCH {
+                best_len = 0;
+                best_offset = 0;
+            }
+            write_varint(&mut res, best_offset);
+            if best_offset > 0 {
+                write_varint(&mut res, best_len);
+            }
+            res.push(data[pos + best_len]);
+            for idx in pos..=pos + best_len {
+                if idx + MIN_MATCH <= n {
+                    let key = [data[idx], data[idx + 1], data[idx + 2]];
+                    prev[idx % size] = head.insert(key, idx).unwrap_or(usize::MAX);
+                }
+            }
+            pos += best_len + 1;
+        }
+        res
+    }
+
+    /// Restores the data, None if the bytes are not an output of `encode`
+    pub fn decode(bytes: &[u8]) -> Option<Vec<u8>> {
+        let mut pos = 0;
+        let n = read_varint(bytes, &mut pos)?;
+        // every triple takes at least 2 bytes and restores at most MAX_MATCH + 1
+        if n > (bytes.len() - pos) / 2 * (MAX_MATCH + 1) {
+            return None;
+        }
+        let mut res: Vec<u8> = Vec::with_capacity(n);
+        while res.len() < n {
+            let offset = read_varint(bytes, &mut pos)?;
+            let len = if offset > 0 { read_varint(bytes, &mut pos)? } else { 0 };
+            if offset > res.len() || len > MAX_MATCH || len >= n - res.len() {
+                return None;
+            }
+            let start = res.len() - offset;
+            for idx in 0..len {
+                let byte = res[start + idx];
+                res.push(byte);
+            }
+            res.push(*bytes.get(pos)?);
+            pos += 1;
+        }
+        if pos != bytes.len() {
+            return None;
+        }
+        Some(res)
+    }
+}
+
+/// LZ78 compression: the data is encoded as the pairs (index of the known phrase, next byte),
+/// every pair adds a new phrase to the dictionary
+///```
+/// use librualg::lz::Lz78;
+///
+/// let data = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+/// let encoded = Lz78::encode(data);
+/// assert!(encoded.len() < data.len());
+/// assert_eq!(Lz78::decode(&encoded), Some(data.to_vec()));
+/// ```
+pub struct Lz78;
+
+impl Lz78 {
+    pub fn encode(data: &[u8]) -> Vec<u8> {
+        let mut res = vec![];
+        write_varint(&mut res, data.len());
+        // (phrase, next byte) -> phrase, the phrase 0 is empty
+        let mut dict: BTreeMap<(usize, u8), usize> = BTreeMap::new();
+        let mut phrase = 0;
+        for &byte in data {
+            match dict.get(&(phrase, byte)) {
+                Some(&next) => phrase = next,
+                None => {
+                    let len = dict.len();
+                    dict.insert((phrase, byte), len + 1);
+                    write_varint(&mut res, phrase);
+                    res.push(byte);
+                    phrase = 0;
+                }
+            }
+        }
+        if phrase != 0 {
+            write_varint(&mut res, phrase);
+        }
+        res
+    }
+
+    /// Restores the data, None if the bytes are not an output of `encode`
+    pub fn decode(bytes: &[u8]) -> Option<Vec<u8>> {
+        let mut pos = 0;
+        let n = read_varint(bytes, &mut pos)?;
+        // (previous phrase, last byte, length)
+        let mut phrases: Vec<(usize, u8, usize)> = vec![(0, 0, 0)];
+        let mut res: Vec<u8> = Vec::with_capacity(n.min(bytes.len() * 8));
+        let mut buf = vec![];
+        while res.len() < n {
+            let idx = read_varint(bytes, &mut pos)?;
+            if idx >= phrases.len() || phrases[idx].2 > n - res.len() {
+                return None;
+            }
+            buf.clear();
+            let mut cur = idx;
+            while cur != 0 {
+                buf.push(phrases[cur].1);
+                cur = phrases[cur].0;
+            }
+            res.extend(buf.iter().rev());
+            if res.len() == n {
+                break;
+            }
+            let byte = *bytes.get(pos)?;
+            pos += 1;
+            res.push(byte);
+            phrases.push((idx, byte, phrases[idx].2 + 1));
+        }
+        if pos != bytes.len() {
+            return None;
+        }
+        Some(res)
+    }
+}
+
+// codes of the variable width, the most significant bit first
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: usize,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: usize, width: usize) {
+        for bit in (0..width).rev() {
+            if self.bits == self.bytes.len() * 8 {
+                self.bytes.push(0);
+            }
+            if value >> bit & 1 == 1 {
+                *self.bytes.last_mut().unwrap() |= 128 >> (self.bits % 8);
+            }
+            self.bits += 1;
+        }
+    }
+}
+
+fn read_bits(bytes: &[u8], bit_pos: &mut usize, width: usize) -> Option<usize> {
+    let mut res = 0;
+    for _ in 0..width {
+        let byte = *bytes.get(*bit_pos / 8)?;
+        res = res << 1 | (byte >> (7 - *bit_pos % 8) & 1) as usize;
+        *bit_pos += 1;
+    }
+    Some(res)
+}
+
+// width of the code number `idx` (from 0): the encoder has 256 + idx phrases at that moment
+fn code_width(idx: usize) -> usize {
+    (usize::MAX.count_ones() - (256 + idx).leading_zeros()) as usize
+}
+
+/// LZW compression: the dictionary starts with all the bytes, the codes have a growing bit width
+///```
+/// use librualg::lz::Lzw;
+///
+/// let data = b"TOBEORNOTTOBEORTOBEORNOT";
+/// let encoded = Lzw::encode(data);
+/// assert!(encoded.len() < data.len());
+/// assert_eq!(Lzw::decode(&encoded), Some(data.to_vec()));
+/// ```
+pub struct Lzw;
+
+impl Lzw {
+    pub fn encode(data: &[u8]) -> Vec<u8> {
+        let mut dict: BTreeMap<(usize, u8), usize> = BTreeMap::new();
+        let mut codes = vec![];
+        let mut iter = data.iter();
+        if let Some(&first) = iter.next() {
+            let mut phrase = first as usize;
+            for &byte in iter {
+                match dict.get(&(phrase, byte)) {
+                    Some(&next) => phrase = next,
+                    None => {
+                        codes.push(phrase);
+                        dict.insert((phrase, byte), 256 + dict.len());
+                        phrase = byte as usize;
+                    }
+                }
+            }
+            codes.push(phrase);
+        }
+        let mut writer = BitWriter { bytes: vec![], bits: 0 };
+        write_varint(&mut writer.bytes, codes.len());
+        writer.bits = writer.bytes.len() * 8;
+        for (idx, code) in codes.iter().enumerate() {
+            writer.write(*code, code_width(idx));
+        }
+        writer.bytes
+    }
+
+    /// Restores the data, None if the bytes are not an output of `encode`
+    pub fn decode(bytes: &[u8]) -> Option<Vec<u8>> {
+        let mut pos = 0;
+        let count = read_varint(bytes, &mut pos)?;
+        let mut bit_pos = pos * 8;
+        // (previous phrase, last byte, first byte) for the codes from 256
+        let mut phrases: Vec<(usize, u8, u8)> = vec![];
+        let first_byte = |phrases: &Vec<(usize, u8, u8)>, code: usize| if code < 256 { code as u8 } else { phrases[code - 256].2 };
+        let mut res = vec![];
+        let mut buf = vec![];
+        let mut prev: Option<usize> = None;
+        for idx in 0..count {
+            let code = read_bits(bytes, &mut bit_pos, code_width(idx))?;
+            let known = 256 + phrases.len();
+            match prev {
+                None if code < 256 => {}
+                None => return None,
+                Some(prev) => {
+                    let first = if code < known {
+                        first_byte(&phrases, code)
+                    } else if code == known {
+                        // the phrase is being defined: previous phrase + its first byte
+                        first_byte(&phrases, prev)
+                    } else {
+                        return None;
+                    };
+                    phrases.push((prev, first, first_byte(&phrases, prev)));
+                }
+            }
+            buf.clear();
+            let mut cur = code;
+            while cur >= 256 {
+                buf.push(phrases[cur - 256].1);
+                cur = phrases[cur - 256].0;
+            }
+            buf.push(cur as u8);
+            res.extend(buf.iter().rev());
+            prev = Some(code);
+        }
+        if bit_pos.div_ceil(8) != bytes.len() {
+            return None;
+        }
+        Some(res)
+    }
+}
+
+#[cfg(test)]
+fn samples() -> Vec<Vec<u8>> {
+    use rand::{Rng, SeedableRng, StdRng};
+
+    let mut rng = StdRng::from_seed(&[49]);
+    let mut res: Vec<Vec<u8>> = vec![
+        vec![],
+        b"a".to_vec(),
+        b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_vec(),
+        b"TOBEORNOTTOBEORTOBEORNOT".to_vec(),
+        b"abababababababababababc".to_vec(),
+        (0..=255u8).collect(),
+    ];
+    for _ in 0..50 {
+        let alphabet = rng.gen_range(1, 5);
+        res.push((0..rng.gen_range(0, 300)).map(|_| rng.gen_range(0, alphabet) as u8).collect());
+    }
+    res.push((0..5000).map(|_| rng.gen::<u8>()).collect());
+    res
+}
+
+#[test]
+fn test_lz77() {
+    for window in &[1, 4, 32, 4096] {
+        let lz = Lz77::new(*window);
+        for data in samples() {
+            assert_eq!(Lz77::decode(&lz.encode(&data)), Some(data));
+        }
+    }
+    let encoded = Lz77::new(1024).encode(&[b'x'; 1000]);
+    assert!(encoded.len() < 30);
+    assert_eq!(Lz77::decode(&[]), None);
+    assert_eq!(Lz77::decode(&[3, 0, b'a']), None);
+    assert_eq!(Lz77::decode(&[3, 1, 5, b'a']), None);
+    let data = vec![b'x'; 10_000];
+    let encoded = Lz77::new(1 << 16).encode(&data);
+    assert_eq!(Lz77::decode(&encoded), Some(data));
+    // 2^34 bytes declared by a few bytes: a literal and a match of 2^34 - 2 bytes
+    let mut bytes = vec![];
+    write_varint(&mut bytes, 1 << 34);
+    bytes.extend_from_slice(&[0, b'a', 1]);
+    write_varint(&mut bytes, (1 << 34) - 2);
+    bytes.push(b'a');
+    assert_eq!(Lz77::decode(&bytes), None);
+    let mut bytes = vec![];
+    write_varint(&mut bytes, MAX_MATCH + 3);
+    bytes.extend_from_slice(&[0, b'a', 1]);
+    write_varint(&mut bytes, MAX_MATCH + 1);
+    bytes.push(b'a');
+    assert_eq!(Lz77::decode(&bytes), None);
+}
+
+#[test]
+fn test_lz78() {
+    for data in samples() {
+        assert_eq!(Lz78::decode(&Lz78::encode(&data)), Some(data));
+    }
+    assert_eq!(Lz78::decode(&[]), None);
+    assert_eq!(Lz78::decode(&[2, 1, b'a']), None);
+    assert_eq!(Lz78::decode(&[1, 0, b'a', 0]), None);
+}
+
+#[test]
+fn test_lzw() {
+    for data in samples() {
+        assert_eq!(Lzw::decode(&Lzw::encode(&data)), Some(data));
+    }
+    assert_eq!(Lzw::decode(&[]), None);
+    assert_eq!(Lzw::decode(&[1]), None);
+    // the code 300 is not defined yet
+    let mut writer = BitWriter { bytes: vec![2], bits: 8 };
+    writer.write(b'a' as usize, code_width(0));
+    writer.write(300, code_width(1));
+    assert_eq!(Lzw::decode(&writer.bytes), None);
+}
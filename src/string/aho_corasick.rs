@@ -0,0 +1,366 @@
+use std::collections::VecDeque;
+
+/// Which matches are reported by `AhoCorasick`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchKind {
+    /// All occurrences of all patterns, the matches may overlap
+    Overlapping,
+    /// Non-overlapping matches scanning from left to right: the leftmost match,
+    /// among the matches starting at the same position the pattern given first wins
+    LeftmostFirst,
+    /// Non-overlapping matches scanning from left to right: the leftmost match,
+    /// among the matches starting at the same position the longest one wins
+    LeftmostLongest,
+}
+
+/// Occurrence of the pattern with the index `pattern` at the bytes `start..end`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Compiled Aho-Corasick automaton (a dense DFA over byte classes) for a set of patterns.
+/// The bytes that do not occur in the patterns share one class, so a state takes
+/// one transition per distinct pattern byte instead of 256.
+/// It is built once and can be used for any number of texts and streams.
+/// Empty patterns never match.
+///```
+/// use librualg::string::{AhoCorasick, MatchKind, Match};
+///
+/// let ac = AhoCorasick::new(&["he", "she", "his", "hers"]);
+/// let res: Vec<(usize, usize)> = ac.find_all("ushers").iter().map(|m| (m.pattern, m.start)).collect();
+/// assert_eq!(res, vec![(1, 1), (0, 2), (3, 2)]);
+///
+/// let ac = AhoCorasick::with_options(&["Samwise", "Sam"], MatchKind::LeftmostFirst, true);
+/// assert_eq!(ac.find("SAMWISE"), Some(Match { pattern: 0, start: 0, end: 7 }));
+/// let ac = AhoCorasick::with_options(&["Sam", "Samwise"], MatchKind::LeftmostFirst, true);
+/// assert_eq!(ac.find("SAMWISE"), Some(Match { pattern: 0, start: 0, end: 3 }));
+/// let ac = AhoCorasick::with_options(&["Sam", "Samwise"], MatchKind::LeftmostLongest, false);
+/// assert_eq!(ac.find("Samwise"), Some(Match { pattern: 1, start: 0, end: 7 }));
+/// ```
+pub struct AhoCorasick {
+    // classes[byte]: the class of the byte, 0 for the bytes which do not occur in the patterns
+    classes: Vec<usize>,
+    // number of the classes
+    alphabet: usize,
+    // transitions[state * alphabet + class]
+    transitions: Vec<usize>,
+    // patterns ending at the state
+    outputs: Vec<Vec<usize>>,
+    // the nearest state on the suffix link chain with outputs (0 if there is none)
+    output_link: Vec<usize>,
+    depth: Vec<usize>,
+    lengths: Vec<usize>,
+    kind: MatchKind,
+}
+
+impl AhoCorasick {
+    /// Overlapping case-sensitive matching
+    pub fn new(patterns: &[&str]) -> Self {
+        AhoCorasick::with_options(patterns, MatchKind::Overlapping, false)
+    }
+
+    pub fn with_options(patterns: &[&str], kind: MatchKind, ascii_case_insensitive: bool) -> Self {
+        let normalize = |ch: u8| if ascii_case_insensitive { ch.to_ascii_lowercase() } else { ch };
+        let mut classes = vec![0; 256];
+        for pattern in patterns {
+            for ch in pattern.bytes() {
+                classes[normalize(ch) as usize] = 1;
+            }
+        }
+        let mut alphabet = 1;
+        for class in classes.iter_mut() {
+            if *class != 0 {
+                *class = alphabet;
+                alphabet += 1;
+            }
+        }
+        if ascii_case_insensitive {
+            for ch in b'A'..=b'Z' {
+                classes[ch as usize] = classes[ch.to_ascii_lowercase() as usize];
+            }
+        }
+        let mut transitions = vec![usize::MAX; alphabet];
+        let mut outputs = vec![vec![]];
+        let mut depth = vec![0];
+        for (idx, pattern) in patterns.iter().enumerate() {
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut state = 0;
+            for ch in pattern.bytes() {
+                let ch = classes[ch as usize];
+                if transitions[state * alphabet + ch] == usize::MAX {
+                    transitions[state * alphabet + ch] = outputs.len();
+                    transitions.extend(vec![usize::MAX; alphabet]);
+                    outputs.push(vec![]);
+                    depth.push(depth[state] + 1);
+                }
+                state = transitions[state * alphabet + ch];
+            }
+            outputs[state].push(idx);
+        }
+
+        let mut link = vec![0; outputs.len()];
+        let mut output_link = vec![0; outputs.len()];
+        let mut queue = VecDeque::new();
+        for next in transitions.iter_mut().take(alphabet) {
+            match *next {
+                usize::MAX => *next = 0,
+                state => queue.push_back(state),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            output_link[state] = if outputs[link[state]].is_empty() { output_link[link[state]] } else { link[state] };
+            for ch in 0..alphabet {
+                let fallback = transitions[link[state] * alphabet + ch];
+                match transitions[state * alphabet + ch] {
+                    usize::MAX => transitions[state * alphabet + ch] = fallback,
+                    next => {
+                        link[next] = fallback;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        let lengths = patterns.iter().map(|pattern| pattern.len()).collect();
+        AhoCorasick { classes, alphabet, transitions, outputs, output_link, depth, lengths, kind }
+    }
+
+    /// All matches in the text according to the match kind, ordered by the end
+    /// (for the overlapping matches with the same end the longest goes first)
+    pub fn find_all(&self, text: &str) -> Vec<Match> {
+        let mut searcher = self.stream();
+        let mut res = searcher.feed(text.as_bytes());
+        res.extend(searcher.finish());
+        res
+    }
+
+    /// The first match of `find_all`
+    pub fn find(&self, text: &str) -> Option<Match> {
+        let mut searcher = self.stream();
+        let mut res = searcher.feed(text.as_bytes());
+        if res.is_empty() {
+            res = searcher.finish();
+        }
+        res.first().cloned()
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        let mut state = 0;
+        for ch in text.bytes() {
+            state = self.transitions[state * self.alphabet + self.classes[ch as usize]];
+            if !self.outputs[state].is_empty() || self.output_link[state] != 0 {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Searcher for a text coming in chunks: the positions of the matches are counted from the start of the stream
+    ///```
+    /// use librualg::string::{AhoCorasick, MatchKind};
+    ///
+    /// let ac = AhoCorasick::with_options(&["ERROR", "WARN"], MatchKind::LeftmostFirst, false);
+    /// let mut searcher = ac.stream();
+    /// let mut res = vec![];
+    /// for chunk in &["INFO ok\nWA", "RN disk\nERR", "OR fail\n"] {
+    ///     res.extend(searcher.feed(chunk.as_bytes()).into_iter().map(|m| (m.pattern, m.start)));
+    /// }
+    /// res.extend(searcher.finish().into_iter().map(|m| (m.pattern, m.start)));
+    /// assert_eq!(res, vec![(1, 8), (0, 18)]);
+    /// ```
+    pub fn stream(&self) -> StreamSearcher<'_> {
+        StreamSearcher { automaton: self, state: 0, pos: 0, buffer: VecDeque::new(), buffer_start: 0, candidate: None }
+    }
+}
+
+/// Search state between the chunks of a stream (see `AhoCorasick::stream`)
+pub struct StreamSearcher<'a> {
+    automaton: &'a AhoCorasick,
+    state: usize,
+    // position of the next byte to process
+    pos: usize,
+    // the bytes that may be scanned again after a leftmost match is chosen
+    buffer: VecDeque<u8>,
+    buffer_start: usize,
+    // the best leftmost match found so far, not reported yet
+    candidate: Option<Match>,
+}
+
+impl<'a> StreamSearcher<'a> {
+    /// Processes the next chunk and returns the matches that are already known to be final.
+    /// For the leftmost match kinds a match may be reported with a delay of up to the length of the longest pattern.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Match> {
+        let mut res = vec![];
+        self.buffer.extend(chunk);
+        self.run(&mut res);
+        res
+    }
+
+    /// Ends the stream and returns the remaining matches
+    pub fn finish(mut self) -> Vec<Match> {
+        let mut res = vec![];
+        while let Some(candidate) = self.candidate.take() {
+            self.restart(candidate, &mut res);
+            self.run(&mut res);
+        }
+        res
+    }
+
+    fn restart(&mut self, candidate: Match, res: &mut Vec<Match>) {
+        res.push(candidate);
+        self.pos = candidate.end;
+        self.state = 0;
+    }
+
+    fn better(&self, other: &Match, current: &Match) -> bool {
+        if other.start != current.start {
+            return other.start < current.start;
+        }
+        match self.automaton.kind {
+            MatchKind::LeftmostLongest if other.end != current.end => other.end > current.end,
+            _ => other.pattern < current.pattern,
+        }
+    }
+
+    fn run(&mut self, res: &mut Vec<Match>) {
+        let ac = self.automaton;
+        while self.pos < self.buffer_start + self.buffer.len() {
+            let ch = self.buffer[self.pos - self.buffer_start];
+            self.state = ac.transitions[self.state * ac.alphabet + ac.classes[ch as usize]];
+            self.pos += 1;
+            let mut state = if ac.outputs[self.state].is_empty() { ac.output_link[self.state] } else { self.state };
+            while state != 0 {
+                for pattern in &ac.outputs[state] {
+                    let found = Match { pattern: *pattern, start: self.pos - ac.lengths[*pattern], end: self.pos };
+                    if ac.kind == MatchKind::Overlapping {
+                        res.push(found);
+                    } else {
+                        match self.candidate {
+                            Some(candidate) if !self.better(&found, &candidate) => {}
+                            _ => self.candidate = Some(found),
+                        }
+                    }
+                }
+                state = ac.output_link[state];
+            }
+            // no match starting at the candidate or before it is possible any more
+            if let Some(candidate) = self.candidate {
+                if self.pos - ac.depth[self.state] > candidate.start {
+                    self.candidate = None;
+                    self.restart(candidate, res);
+                }
+            }
+        }
+        // a future leftmost match starts not before the longest partial match
+        let keep_from = if ac.kind == MatchKind::Overlapping { self.pos } else { self.pos - ac.depth[self.state] };
+        self.buffer.drain(..keep_from - self.buffer_start);
+        self.buffer_start = keep_from;
+    }
+}
+
+#[cfg(test)]
+fn brute_force(patterns: &[&str], text: &str, kind: MatchKind) -> Vec<Match> {
+    let text = text.as_bytes();
+    let mut all = vec![];
+    for end in 1..=text.len() {
+        let mut ending = vec![];
+        for (idx, pattern) in patterns.iter().enumerate() {
+            if !pattern.is_empty() && pattern.len() <= end && &text[end - pattern.len()..end] == pattern.as_bytes() {
+                ending.push(Match { pattern: idx, start: end - pattern.len(), end });
+            }
+        }
+        ending.sort_by(|a, b| a.start.cmp(&b.start).then(a.pattern.cmp(&b.pattern)));
+        all.extend(ending);
+    }
+    if kind == MatchKind::Overlapping {
+        return all;
+    }
+    let mut res = vec![];
+    let mut pos = 0;
+    loop {
+        let best = all.iter().filter(|m| m.start >= pos).min_by(|a, b| {
+            let order = match kind {
+                MatchKind::LeftmostLongest => b.end.cmp(&a.end),
+                _ => std::cmp::Ordering::Equal,
+            };
+            a.start.cmp(&b.start).then(order).then(a.pattern.cmp(&b.pattern))
+        });
+        match best {
+            Some(m) => {
+                res.push(*m);
+                pos = m.end;
+            }
+            None => break,
+        }
+    }
+    res
+}
+
+#[test]
+fn test_aho_corasick_kinds() {
+    use rand::{Rng, SeedableRng, StdRng};
+
+    let mut rng = StdRng::from_seed(&[11]);
+    for _ in 0..300 {
+        let count = rng.gen_range(1, 6);
+        let patterns: Vec<String> = (0..count).map(|_| {
+            let len = rng.gen_range(0, 5);
+            (0..len).map(|_| (b'a' + rng.gen_range(0, 3)) as char).collect()
+        }).collect();
+        let patterns: Vec<&str> = patterns.iter().map(|s| s.as_str()).collect();
+        let len = rng.gen_range(0, 40);
+        let text: String = (0..len).map(|_| (b'a' + rng.gen_range(0, 3)) as char).collect();
+        for kind in &[MatchKind::Overlapping, MatchKind::LeftmostFirst, MatchKind::LeftmostLongest] {
+            let ac = AhoCorasick::with_options(&patterns, *kind, false);
+            let expected = brute_force(&patterns, &text, *kind);
+            let mut found = ac.find_all(&text);
+            if *kind == MatchKind::Overlapping {
+                found.sort_by(|a, b| a.end.cmp(&b.end).then(a.start.cmp(&b.start)).then(a.pattern.cmp(&b.pattern)));
+            }
+            assert_eq!(found, expected);
+            assert_eq!(ac.find(&text), expected.first().cloned());
+            assert_eq!(ac.is_match(&text), !expected.is_empty());
+
+            // the same matches for any split of the text into chunks
+            let mut searcher = ac.stream();
+            let mut streamed = vec![];
+            let mut pos = 0;
+            while pos < text.len() {
+                let next = (pos + rng.gen_range(0, 6)).min(text.len());
+                streamed.extend(searcher.feed(&text.as_bytes()[pos..next]));
+                pos = next;
+            }
+            streamed.extend(searcher.finish());
+            assert_eq!(streamed, ac.find_all(&text));
+        }
+    }
+}
+
+#[test]
+fn test_aho_corasick_case_insensitive() {
+    let ac = AhoCorasick::with_options(&["error", "Timeout"], MatchKind::LeftmostFirst, true);
+    let res: Vec<(usize, usize)> = ac.find_all("ERROR: timeout; Error: TIMEOUT").iter().map(|m| (m.pattern, m.start)).collect();
+    assert_eq!(res, vec![(0, 0), (1, 7), (0, 16), (1, 23)]);
+    let ac = AhoCorasick::new(&["error"]);
+    assert!(!ac.is_match("ERROR"));
+    assert!(ac.is_match("an error"));
+    assert_eq!(AhoCorasick::new(&[]).find_all("abc"), vec![]);
+    assert_eq!(AhoCorasick::new(&[""]).find("abc"), None);
+}
+
+#[test]
+fn test_aho_corasick_byte_classes() {
+    let ac = AhoCorasick::new(&["cat", "act", "tac"]);
+    // 'a', 'c', 't' and the class of all other bytes
+    assert_eq!(ac.alphabet, 4);
+    assert_eq!(ac.transitions.len(), ac.outputs.len() * 4);
+    let res: Vec<(usize, usize)> = ac.find_all("xcatacty").iter().map(|m| (m.pattern, m.start)).collect();
+    assert_eq!(res, vec![(0, 1), (2, 3), (1, 4)]);
+    let ac = AhoCorasick::with_options(&["Ab"], MatchKind::Overlapping, true);
+    assert_eq!(ac.alphabet, 3);
+    assert_eq!(ac.find_all("aB ab AB").len(), 3);
+}
@@ -0,0 +1,301 @@
+use std::cmp::max;
+use std::ops::Range;
+
+const NEG: i64 = i64::MIN / 4;
+
+/// Scores of aligning two symbols
+///```
+/// use librualg::string::SubstitutionMatrix;
+///
+/// let mut matrix = SubstitutionMatrix::new(2, -1);
+/// matrix.set(b'A', b'G', 1);
+/// assert_eq!(matrix.score(b'A', b'A'), 2);
+/// assert_eq!(matrix.score(b'G', b'A'), 1);
+/// assert_eq!(matrix.score(b'A', b'C'), -1);
+///
+/// let matrix = SubstitutionMatrix::from_table("AC", &[&[3, -2], &[-2, 4]], -5);
+/// assert_eq!(matrix.score(b'C', b'C'), 4);
+/// assert_eq!(matrix.score(b'A', b'T'), -5);
+/// ```
+#[derive(Clone)]
+pub struct SubstitutionMatrix {
+    scores: Vec<i32>,
+}
+
+impl SubstitutionMatrix {
+    /// `match_score` for equal symbols, `mismatch_score` for different ones
+    pub fn new(match_score: i32, mismatch_score: i32) -> Self {
+        let mut scores = vec![mismatch_score; 256 * 256];
+        for ch in 0..256 {
+            scores[ch * 256 + ch] = match_score;
+        }
+        SubstitutionMatrix { scores }
+    }
+
+    /// Matrix given as a table over the alphabet (like BLOSUM or PAM).
+    /// `scores[i][j]` is the score of the symbols `alphabet[i]` and `alphabet[j]`,
+    /// the pairs with the symbols outside of the alphabet get `default_score`.
+    pub fn from_table(alphabet: &str, scores: &[&[i32]], default_score: i32) -> Self {
+        let mut matrix = SubstitutionMatrix { scores: vec![default_score; 256 * 256] };
+        for (i, a) in alphabet.bytes().enumerate() {
+            for (j, b) in alphabet.bytes().enumerate() {
+                matrix.scores[a as usize * 256 + b as usize] = scores[i][j];
+            }
+        }
+        matrix
+    }
+
+    /// Sets the score of the pair in both orders
+    pub fn set(&mut self, a: u8, b: u8, score: i32) {
+        self.scores[a as usize * 256 + b as usize] = score;
+        self.scores[b as usize * 256 + a as usize] = score;
+    }
+
+    pub fn score(&self, a: u8, b: u8) -> i32 {
+        self.scores[a as usize * 256 + b as usize]
+    }
+}
+
+/// Affine gap penalty: a gap of length `k` costs `open + extend * (k - 1)`.
+/// `open == extend` gives linear gaps.
+#[derive(Clone, Copy, Debug)]
+pub struct GapPenalty {
+    pub open: i32,
+    pub extend: i32,
+}
+
+/// Result of an alignment: the score, the aligned sequences with `-` in the gaps
+/// and the aligned parts of the source sequences.
+/// A `-` of the source can not be told apart from a gap in `first` and `second`, the ranges and the score are exact.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alignment {
+    pub score: i32,
+    pub first: Vec<u8>,
+    pub second: Vec<u8>,
+    pub first_range: Range<usize>,
+    pub second_range: Range<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Pair,
+    GapSecond,
+    GapFirst,
+}
+
+/// Global alignment (Needleman–Wunsch with Gotoh's affine gaps) of the byte sequences
+///```
+/// use librualg::string::{needleman_wunsch, SubstitutionMatrix, GapPenalty};
+///
+/// let matrix = SubstitutionMatrix::new(1, -1);
+/// let res = needleman_wunsch(b"GATTACA", b"GCATGCU", &matrix, GapPenalty { open: 1, extend: 1 });
+/// assert_eq!(res.score, 0);
+/// assert_eq!(res.first.len(), res.second.len());
+///
+/// let res = needleman_wunsch(b"ACGTTTTACG", b"ACGACG", &matrix, GapPenalty { open: 3, extend: 1 });
+/// assert_eq!(res.score, 6 - 3 - 3);
+/// assert_eq!(res.first, b"ACGTTTTACG");
+/// assert_eq!(res.second, b"ACG----ACG");
+/// ```
+pub fn needleman_wunsch(first: &[u8], second: &[u8], matrix: &SubstitutionMatrix, gap: GapPenalty) -> Alignment {
+    align(first, second, matrix, gap, false)
+}
+
+/// Local alignment (Smith–Waterman with Gotoh's affine gaps): the best scoring pair of the subsequences of consecutive bytes
+///```
+/// use librualg::string::{smith_waterman, SubstitutionMatrix, GapPenalty};
+///
+/// let matrix = SubstitutionMatrix::new(3, -3);
+/// let res = smith_waterman(b"TGTTACGG", b"GGTTGACTA", &matrix, GapPenalty { open: 2, extend: 2 });
+/// assert_eq!(res.score, 13);
+/// assert_eq!(res.first, b"GTT-AC");
+/// assert_eq!(res.second, b"GTTGAC");
+/// assert_eq!(res.first_range, 1..6);
+/// assert_eq!(res.second_range, 1..7);
+/// ```
+pub fn smith_waterman(first: &[u8], second: &[u8], matrix: &SubstitutionMatrix, gap: GapPenalty) -> Alignment {
+    align(first, second, matrix, gap, true)
+}
+
+fn align(a: &[u8], b: &[u8], matrix: &SubstitutionMatrix, gap: GapPenalty, local: bool) -> Alignment {
+    let (n, m) = (a.len(), b.len());
+    let (open, extend) = (gap.open as i64, gap.extend as i64);
+    // pair[i][j]: a[i - 1] is aligned with b[j - 1],
+    // gap_second[i][j]: a[i - 1] is aligned with a gap, gap_first[i][j]: b[j - 1] is aligned with a gap
+    let mut pair = vec![vec![NEG; m + 1]; n + 1];
+    let mut gap_second = vec![vec![NEG; m + 1]; n + 1];
+    let mut gap_first = vec![vec![NEG; m + 1]; n + 1];
+    if !local {
+        pair[0][0] = 0;
+    }
+    let mut best = (0, 0, 0);
+    for i in 0..=n {
+        for j in 0..=m {
+            if i > 0 && j > 0 {
+                let prev = max(pair[i - 1][j - 1], max(gap_second[i - 1][j - 1], gap_first[i - 1][j - 1]));
+                let prev = if local { max(prev, 0) } else { prev };
+                pair[i][j] = prev + matrix.score(a[i - 1], b[j - 1]) as i64;
+                if local && pair[i][j] > best.0 {
+                    best = (pair[i][j], i, j);
+                }
+            }
+            if i > 0 {
+                gap_second[i][j] = max(max(pair[i - 1][j], gap_first[i - 1][j]) - open, gap_second[i - 1][j] - extend);
+            }
+            if j > 0 {
+                gap_first[i][j] = max(max(pair[i][j - 1], gap_second[i][j - 1]) - open, gap_first[i][j - 1] - extend);
+            }
+        }
+    }
+
+    let (score, mut i, mut j, mut state) = if local {
+        (best.0, best.1, best.2, State::Pair)
+    } else {
+        let mut res = (pair[n][m], n, m, State::Pair);
+        if gap_second[n][m] > res.0 {
+            res = (gap_second[n][m], n, m, State::GapSecond);
+        }
+        if gap_first[n][m] > res.0 {
+            res = (gap_first[n][m], n, m, State::GapFirst);
+        }
+        res
+    };
+    let (end_i, end_j) = (i, j);
+    let mut first = vec![];
+    let mut second = vec![];
+    while (local && score > 0) || (!local && (i > 0 || j > 0)) {
+        let (pi, pj) = match state {
+            State::Pair => {
+                first.push(a[i - 1]);
+                second.push(b[j - 1]);
+                (i - 1, j - 1)
+            }
+            State::GapSecond => {
+                first.push(a[i - 1]);
+                second.push(b'-');
+                (i - 1, j)
+            }
+            State::GapFirst => {
+                first.push(b'-');
+                second.push(b[j - 1]);
+                (i, j - 1)
+            }
+        };
+        state = match state {
+            State::Pair => {
+                let value = pair[i][j] - matrix.score(a[i - 1], b[j - 1]) as i64;
+                if local && value == 0 {
+                    i = pi;
+                    j = pj;
+                    break;
+                }
+                if pair[pi][pj] == value {
+                    State::Pair
+                } else if gap_second[pi][pj] == value {
+                    State::GapSecond
+                } else {
+                    State::GapFirst
+                }
+            }
+            State::GapSecond => {
+                let value = gap_second[i][j];
+                if gap_second[pi][pj] - extend == value {
+                    State::GapSecond
+                } else if pair[pi][pj] - open == value {
+                    State::Pair
+                } else {
+                    State::GapFirst
+                }
+            }
+            State::GapFirst => {
+                let value = gap_first[i][j];
+                if gap_first[pi][pj] - extend == value {
+                    State::GapFirst
+                } else if pair[pi][pj] - open == value {
+                    State::Pair
+                } else {
+                    State::GapSecond
+                }
+            }
+        };
+        i = pi;
+        j = pj;
+    }
+    first.reverse();
+    second.reverse();
+    Alignment {
+        score: score as i32,
+        first,
+        second,
+        first_range: i..end_i,
+        second_range: j..end_j,
+    }
+}
+
+#[cfg(test)]
+fn alignment_score(res: &Alignment, matrix: &SubstitutionMatrix, gap: GapPenalty) -> i32 {
+    let (a, b) = (&res.first, &res.second);
+    assert_eq!(a.len(), b.len());
+    let mut score = 0;
+    for k in 0..a.len() {
+        assert!(a[k] != b'-' || b[k] != b'-');
+        if a[k] == b'-' {
+            score -= if k > 0 && a[k - 1] == b'-' { gap.extend } else { gap.open };
+        } else if b[k] == b'-' {
+            score -= if k > 0 && b[k - 1] == b'-' { gap.extend } else { gap.open };
+        } else {
+            score += matrix.score(a[k], b[k]);
+        }
+    }
+    score
+}
+
+#[test]
+fn test_needleman_wunsch() {
+    let matrix = SubstitutionMatrix::new(2, -1);
+    let pairs: [(&[u8], &[u8]); 7] = [(b"", b""), (b"", b"ACG"), (b"ACG", b""), (b"GATTACA", b"GCATGCU"), (b"AGTACGCA", b"TATGC"), (b"AAAA", b"A"), (b"ACGTTTTACG", b"ACGACG")];
+    for gap in &[GapPenalty { open: 1, extend: 1 }, GapPenalty { open: 4, extend: 1 }, GapPenalty { open: 2, extend: 0 }] {
+        for (a, b) in &pairs {
+            let res = needleman_wunsch(a, b, &matrix, *gap);
+            assert_eq!(res.first.iter().filter(|&&ch| ch != b'-').cloned().collect::<Vec<u8>>(), *a);
+            assert_eq!(res.second.iter().filter(|&&ch| ch != b'-').cloned().collect::<Vec<u8>>(), *b);
+            assert_eq!(alignment_score(&res, &matrix, *gap), res.score);
+            assert_eq!(res.first_range, 0..a.len());
+        }
+    }
+    // unit costs: the score is minus the levenshtein distance
+    let matrix = SubstitutionMatrix::new(0, -1);
+    let gap = GapPenalty { open: 1, extend: 1 };
+    for (a, b) in &pairs {
+        let res = needleman_wunsch(a, b, &matrix, gap);
+        assert_eq!(-res.score as u32, crate::string::levenshtein_distance_slice(a, b, 1, 1, 1));
+    }
+    // affine gaps prefer one long gap
+    let matrix = SubstitutionMatrix::new(1, -1);
+    let res = needleman_wunsch(b"ACGTTTTACG", b"ACGACG", &matrix, GapPenalty { open: 3, extend: 1 });
+    assert_eq!(res.second, b"ACG----ACG");
+}
+
+#[test]
+fn test_smith_waterman() {
+    let matrix = SubstitutionMatrix::new(3, -3);
+    let gap = GapPenalty { open: 2, extend: 2 };
+    let res = smith_waterman(b"TGTTACGG", b"GGTTGACTA", &matrix, gap);
+    assert_eq!(res.score, 13);
+    assert_eq!(alignment_score(&res, &matrix, gap), 13);
+
+    let res = smith_waterman(b"xxxxHELLOyyyy", b"zzHELLOzz", &matrix, gap);
+    assert_eq!(res.score, 15);
+    assert_eq!(res.first, b"HELLO");
+    assert_eq!(res.first_range, 4..9);
+    assert_eq!(res.second_range, 2..7);
+
+    let res = smith_waterman(b"AAA", b"CCC", &matrix, gap);
+    assert_eq!(res.score, 0);
+    assert_eq!(res.first, b"");
+
+    let gap = GapPenalty { open: 5, extend: 1 };
+    let res = smith_waterman(b"ACGTACGTTTTTTTTACGTACGT", b"ACGTACGTACGTACGT", &matrix, gap);
+    assert_eq!(res.score, 48 - 5 - 6);
+    assert_eq!(alignment_score(&res, &matrix, gap), res.score);
+}
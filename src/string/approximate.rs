@@ -0,0 +1,131 @@
+use std::cmp::min;
+
+/// Approximate (fuzzy) search: all the positions `end` such that some substring `text[start..end]`
+/// is within the Levenshtein distance `k` from the pattern. Returns the pairs (end, the smallest distance).
+/// Uses bitap (Wu–Manber) for patterns of up to 64 bytes and Ukkonen's cutoff dynamic programming for longer ones.
+///```
+/// use librualg::string::approximate_search;
+///
+/// assert_eq!(approximate_search("the quick brwn fox", "brown", 1), vec![(14, 1)]);
+/// assert_eq!(approximate_search("abcdef", "cd", 0), vec![(4, 0)]);
+/// assert_eq!(approximate_search("abcdef", "xyz", 1), vec![]);
+/// ```
+pub fn approximate_search(text: &str, pattern: &str, k: u32) -> Vec<(usize, u32)> {
+    if pattern.len() <= 64 {
+        bitap(text.as_bytes(), pattern.as_bytes(), k as usize)
+    } else {
+        cutoff(text.as_bytes(), pattern.as_bytes(), k as usize)
+    }
+}
+
+fn bitap(text: &[u8], pattern: &[u8], k: usize) -> Vec<(usize, u32)> {
+    let m = pattern.len();
+    if m == 0 {
+        return (0..=text.len()).map(|end| (end, 0)).collect();
+    }
+    // more errors than the length of the pattern change nothing
+    let k = min(k, m);
+    let mut masks = [0u64; 256];
+    for (idx, &ch) in pattern.iter().enumerate() {
+        masks[ch as usize] |= 1 << idx;
+    }
+    let last = 1u64 << (m - 1);
+    // bit i of state[d]: pattern[..=i] matches a suffix of the read text with at most d errors
+    let mut state: Vec<u64> = (0..=k).map(|d| if d >= 64 { !0 } else { (1u64 << d) - 1 }).collect();
+    let mut res = vec![];
+    let report = |state: &[u64], end: usize, res: &mut Vec<(usize, u32)>| {
+        if let Some(d) = state.iter().position(|s| s & last != 0) {
+            res.push((end, d as u32));
+        }
+    };
+    report(&state, 0, &mut res);
+    for (idx, &ch) in text.iter().enumerate() {
+        let mut prev_old = state[0];
+        state[0] = ((state[0] << 1) | 1) & masks[ch as usize];
+        for d in 1..=k {
+            let old = state[d];
+            let matched = ((old << 1) | 1) & masks[ch as usize];
+            let replaced = (prev_old << 1) | 1;
+            let inserted = prev_old;
+            let deleted = (state[d - 1] << 1) | 1;
+            state[d] = matched | replaced | inserted | deleted;
+            prev_old = old;
+        }
+        report(&state, idx + 1, &mut res);
+    }
+    res
+}
+
+fn cutoff(text: &[u8], pattern: &[u8], k: usize) -> Vec<(usize, u32)> {
+    let m = pattern.len();
+    let cap = k + 1;
+    // column of the distances, the values above k are kept as k + 1
+    let mut column: Vec<usize> = (0..=m).map(|i| min(i, cap)).collect();
+    // last row with the distance at most k
+    let mut active = min(k, m);
+    let mut res = vec![];
+    if active == m {
+        res.push((0, column[m] as u32));
+    }
+    for (idx, &ch) in text.iter().enumerate() {
+        let mut diagonal = column[0];
+        let bound = min(active + 1, m);
+        for i in 1..=bound {
+            let up = column[i];
+            let cost = if pattern[i - 1] == ch { 0 } else { 1 };
+            column[i] = min(min(diagonal + cost, up + 1), column[i - 1] + 1).min(cap);
+            diagonal = up;
+        }
+        active = bound;
+        while active > 0 && column[active] > k {
+            column[active] = cap;
+            active -= 1;
+        }
+        if active == m {
+            res.push((idx + 1, column[m] as u32));
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+fn approximate_search_naive(text: &str, pattern: &str, k: u32) -> Vec<(usize, u32)> {
+    let mut res = vec![];
+    for end in 0..=text.len() {
+        let best = (0..=end).map(|start| crate::string::levenshtein_distance(&text[start..end], pattern, 1, 1, 1)).min().unwrap();
+        if best <= k {
+            res.push((end, best));
+        }
+    }
+    res
+}
+
+#[test]
+fn test_approximate_search() {
+    use rand::{Rng, StdRng, SeedableRng};
+
+    let mut rng = StdRng::from_seed(&[7]);
+    for _ in 0..300 {
+        let text: String = (0..rng.gen_range(0, 30)).map(|_| (b'a' + rng.gen_range(0, 3) as u8) as char).collect();
+        let pattern: String = (0..rng.gen_range(0, 7)).map(|_| (b'a' + rng.gen_range(0, 3) as u8) as char).collect();
+        let k = rng.gen_range(0, 4);
+        let expected = approximate_search_naive(&text, &pattern, k);
+        assert_eq!(bitap(text.as_bytes(), pattern.as_bytes(), k as usize), expected);
+        assert_eq!(cutoff(text.as_bytes(), pattern.as_bytes(), k as usize), expected);
+    }
+}
+
+#[test]
+fn test_approximate_search_long_pattern() {
+    let pattern: String = (0..100).map(|i| (b'a' + (i * 7 % 26) as u8) as char).collect();
+    let mut text = String::from("xxxx");
+    text.push_str(&pattern[..40]);
+    text.push_str(&pattern[41..70]);
+    text.push('#');
+    text.push_str(&pattern[70..]);
+    text.push_str("yyyy");
+    let res = approximate_search(&text, &pattern, 2);
+    assert_eq!(res, vec![(text.len() - 4, 2)]);
+    assert_eq!(approximate_search(&text, &pattern, 1), vec![]);
+    assert_eq!(&bitap(text.as_bytes(), &pattern.as_bytes()[..64], 3), &cutoff(text.as_bytes(), &pattern.as_bytes()[..64], 3));
+}
@@ -0,0 +1,170 @@
+use std::cmp::max;
+
+#[derive(Clone)]
+enum Rules {
+    BoyerMoore(Vec<usize>),
+    Horspool,
+}
+
+/// Precompiled single-pattern searcher: Boyer–Moore (bad-character and good-suffix rules)
+/// or Boyer–Moore–Horspool (bad-character rule only). Can be reused for many texts.
+///```
+/// use librualg::string::Searcher;
+///
+/// let searcher = Searcher::boyer_moore("abc");
+/// assert_eq!(searcher.search("abcdabcd"), vec![0, 4]);
+/// assert_eq!(searcher.search_first("cbcdabcd"), Some(4));
+///
+/// let searcher = Searcher::horspool("aa");
+/// assert_eq!(searcher.search("aaaa"), vec![0, 1, 2]);
+/// assert_eq!(searcher.search_first("abab"), None);
+/// ```
+#[derive(Clone)]
+pub struct Searcher {
+    pattern: Vec<u8>,
+    // shift of the window by the symbol under its last position
+    bad_char: Vec<usize>,
+    rules: Rules,
+}
+
+impl Searcher {
+    pub fn boyer_moore(pattern: &str) -> Self {
+        let pattern = pattern.as_bytes().to_vec();
+        let good_suffix = Searcher::good_suffix(&pattern);
+        Searcher { bad_char: Searcher::bad_char(&pattern), pattern, rules: Rules::BoyerMoore(good_suffix) }
+    }
+
+    pub fn horspool(pattern: &str) -> Self {
+        let pattern = pattern.as_bytes().to_vec();
+        Searcher { bad_char: Searcher::bad_char(&pattern), pattern, rules: Rules::Horspool }
+    }
+
+    fn bad_char(pattern: &[u8]) -> Vec<usize> {
+        let m = pattern.len();
+        let mut res = vec![m; 256];
+        for (idx, &ch) in pattern.iter().enumerate().take(m.saturating_sub(1)) {
+            res[ch as usize] = m - 1 - idx;
+        }
+        res
+    }
+
+    fn good_suffix(pattern: &[u8]) -> Vec<usize> {
+        let m = pattern.len() as isize;
+        if m == 0 {
+            return vec![];
+        }
+        // suffixes[i]: length of the longest common suffix of pattern[..=i] and pattern
+        let mut suffixes = vec![0isize; m as usize];
+        suffixes[m as usize - 1] = m;
+        let (mut f, mut g) = (0isize, m - 1);
+        for i in (0..m - 1).rev() {
+            if i > g && suffixes[(i + m - 1 - f) as usize] < i - g {
+                suffixes[i as usize] = suffixes[(i + m - 1 - f) as usize];
+            } else {
+                if i < g {
+                    g = i;
+                }
+                f = i;
+                while g >= 0 && pattern[g as usize] == pattern[(g + m - 1 - f) as usize] {
+                    g -= 1;
+                }
+                suffixes[i as usize] = f - g;
+            }
+        }
+        let mut res = vec![m as usize; m as usize];
+        let mut j = 0;
+        for i in (-1..m - 1).rev() {
+            if i == -1 || suffixes[i as usize] == i + 1 {
+                while j < m - 1 - i {
+                    if res[j as usize] == m as usize {
+                        res[j as usize] = (m - 1 - i) as usize;
+                    }
+                    j += 1;
+                }
+            }
+        }
+        for i in 0..m - 1 {
+            res[(m - 1 - suffixes[i as usize]) as usize] = (m - 1 - i) as usize;
+        }
+        res
+    }
+
+    /// Return all occurrences of the pattern
+    pub fn search(&self, text: &str) -> Vec<usize> {
+        let mut res = vec![];
+        self.run(text.as_bytes(), |pos| {
+            res.push(pos);
+            true
+        });
+        res
+    }
+
+    /// Return first occurrence of the pattern
+    pub fn search_first(&self, text: &str) -> Option<usize> {
+        let mut res = None;
+        self.run(text.as_bytes(), |pos| {
+            res = Some(pos);
+            false
+        });
+        res
+    }
+
+    // calls `found` for the occurrences while it returns true
+    fn run<F: FnMut(usize) -> bool>(&self, text: &[u8], mut found: F) {
+        let (n, m) = (text.len(), self.pattern.len());
+        if m == 0 {
+            for pos in 0..=n {
+                if !found(pos) {
+                    return;
+                }
+            }
+            return;
+        }
+        let mut pos = 0;
+        while pos + m <= n {
+            let window = &text[pos..pos + m];
+            match &self.rules {
+                Rules::Horspool => {
+                    if window == self.pattern.as_slice() && !found(pos) {
+                        return;
+                    }
+                    pos += self.bad_char[window[m - 1] as usize];
+                }
+                Rules::BoyerMoore(good_suffix) => {
+                    let mut i = m;
+                    while i > 0 && self.pattern[i - 1] == window[i - 1] {
+                        i -= 1;
+                    }
+                    if i == 0 {
+                        if !found(pos) {
+                            return;
+                        }
+                        pos += good_suffix[0];
+                    } else {
+                        let bad = (self.bad_char[window[i - 1] as usize] + i) as isize - m as isize;
+                        pos += max(good_suffix[i - 1] as isize, bad) as usize;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_searcher() {
+    use rand::{Rng, SeedableRng, StdRng};
+
+    let mut rng = StdRng::from_seed(&[42]);
+    for _ in 0..500 {
+        let text: String = (0..rng.gen_range(0, 60)).map(|_| (b'a' + rng.gen_range(0, 3) as u8) as char).collect();
+        let pattern: String = (0..rng.gen_range(0, 6)).map(|_| (b'a' + rng.gen_range(0, 3) as u8) as char).collect();
+        let expected = crate::string::kmp(&text, &pattern);
+        for searcher in &[Searcher::boyer_moore(&pattern), Searcher::horspool(&pattern)] {
+            assert_eq!(searcher.search(&text), expected);
+            assert_eq!(searcher.search_first(&text), expected.first().cloned());
+        }
+    }
+    let searcher = Searcher::boyer_moore("");
+    assert_eq!(searcher.search("ab"), vec![0, 1, 2]);
+    assert_eq!(searcher.search_first(""), Some(0));
+}
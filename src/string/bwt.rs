@@ -0,0 +1,383 @@
+use crate::string::{suffix_array, suffix_array_slice};
+
+const BLOCK: usize = 64;
+const MAGIC: &[u8; 4] = b"FMI1";
+
+/// Burrows–Wheeler transform: the last column of the sorted cyclic rotations
+/// and the row of the source string among the rotations
+///```
+/// use librualg::string::{bwt, inverse_bwt};
+///
+/// let (last, row) = bwt("banana");
+/// assert_eq!(last, b"nnbaaa".to_vec());
+/// assert_eq!(row, 3);
+/// assert_eq!(inverse_bwt(&last, row), b"banana".to_vec());
+/// ```
+pub fn bwt(src: &str) -> (Vec<u8>, usize) {
+    let bytes = src.as_bytes();
+    let n = bytes.len();
+    if n == 0 {
+        return (vec![], 0);
+    }
+    let (p, _) = suffix_array(src);
+    let mut row = 0;
+    let mut last = Vec::with_capacity(n);
+    for (idx, &start) in p.iter().enumerate() {
+        if start == 0 {
+            row = idx;
+        }
+        last.push(bytes[(start + n - 1) % n]);
+    }
+    (last, row)
+}
+
+/// Restores the string from the last column of the Burrows–Wheeler transform and the row of the string
+pub fn inverse_bwt(last: &[u8], row: usize) -> Vec<u8> {
+    let n = last.len();
+    if n == 0 {
+        return vec![];
+    }
+    let mut start = [0usize; 257];
+    for &ch in last {
+        start[ch as usize + 1] += 1;
+    }
+    for ch in 0..256 {
+        start[ch + 1] += start[ch];
+    }
+    // row of the rotation starting one symbol earlier
+    let mut lf = vec![0; n];
+    for (idx, &ch) in last.iter().enumerate() {
+        lf[idx] = start[ch as usize];
+        start[ch as usize] += 1;
+    }
+    let mut res = vec![0; n];
+    let mut cur = row;
+    for ch in res.iter_mut().rev() {
+        *ch = last[cur];
+        cur = lf[cur];
+    }
+    res
+}
+
+/// FM-index: full-text search over the Burrows–Wheeler transform of the text.
+/// Keeps the transform over the alphabet of the text, the occurrence counts every 64 symbols
+/// and every `sample_rate`-th entry of the suffix array. The text must be shorter than 4 GiB.
+///```
+/// use librualg::string::FmIndex;
+///
+/// let index = FmIndex::build("abracadabra");
+/// assert_eq!(index.count("abra"), 2);
+/// assert_eq!(index.locate("a"), vec![0, 3, 5, 7, 10]);
+/// assert!(!index.contains("abrac!"));
+///
+/// let restored = FmIndex::from_bytes(&index.to_bytes()).unwrap();
+/// assert_eq!(restored.locate("bra"), vec![1, 8]);
+/// ```
+#[derive(Clone)]
+pub struct FmIndex {
+    alphabet: Vec<u8>,
+    codes: Vec<Option<u8>>,
+    // the codes of the symbols, the row `primary` holds the sentinel
+    bwt: Vec<u8>,
+    primary: usize,
+    // number of the suffixes starting with a smaller symbol (the sentinel included)
+    counts: Vec<usize>,
+    occ: Vec<u32>,
+    sample_rate: usize,
+    sampled: Vec<u64>,
+    sampled_rank: Vec<u32>,
+    samples: Vec<u32>,
+}
+
+impl FmIndex {
+    pub fn build(text: &str) -> Self {
+        FmIndex::with_sample_rate(text, 32)
+    }
+
+    /// Smaller `sample_rate` makes `locate` faster and the index larger
+    pub fn with_sample_rate(text: &str, sample_rate: usize) -> Self {
+        assert!(sample_rate > 0, "sample rate must be positive");
+        let bytes = text.as_bytes();
+        assert!(bytes.len() < u32::MAX as usize, "text is too long");
+        let mut present = [false; 256];
+        for &ch in bytes {
+            present[ch as usize] = true;
+        }
+        let alphabet: Vec<u8> = (0..=255u8).filter(|&ch| present[ch as usize]).collect();
+        let codes = FmIndex::codes(&alphabet);
+
+        let mut mapped: Vec<u16> = bytes.iter().map(|&ch| codes[ch as usize].unwrap() as u16 + 1).collect();
+        mapped.push(0);
+        let (sa, _) = suffix_array_slice(&mapped);
+        let mut bwt = Vec::with_capacity(sa.len());
+        let mut primary = 0;
+        let mut sampled = vec![0u64; sa.len() / 64 + 1];
+        let mut samples = vec![];
+        for (row, &start) in sa.iter().enumerate() {
+            if start == 0 {
+                primary = row;
+                bwt.push(0);
+            } else {
+                bwt.push(mapped[start - 1] as u8 - 1);
+            }
+            if start % sample_rate == 0 {
+                sampled[row / 64] |= 1 << (row % 64);
+                samples.push(start as u32);
+            }
+        }
+        FmIndex::finish(alphabet, bwt, primary, sample_rate, sampled, samples)
+    }
+
+    fn codes(alphabet: &[u8]) -> Vec<Option<u8>> {
+        let mut codes = vec![None; 256];
+        for (code, &ch) in alphabet.iter().enumerate() {
+            codes[ch as usize] = Some(code as u8);
+        }
+        codes
+    }
+
+    fn finish(alphabet: Vec<u8>, bwt: Vec<u8>, primary: usize, sample_rate: usize, sampled: Vec<u64>, samples: Vec<u32>) -> Self {
+        let sigma = alphabet.len();
+        let mut occ = Vec::with_capacity((bwt.len() / BLOCK + 2) * sigma);
+        let mut cur = vec![0u32; sigma];
+        for (block, chunk) in bwt.chunks(BLOCK).enumerate() {
+            occ.extend_from_slice(&cur);
+            for (idx, &code) in chunk.iter().enumerate() {
+                if block * BLOCK + idx != primary {
+                    cur[code as usize] += 1;
+                }
+            }
+        }
+        occ.extend_from_slice(&cur);
+        let mut counts = vec![1; sigma];
+        for code in 1..sigma {
+            counts[code] = counts[code - 1] + cur[code - 1] as usize;
+        }
+        let mut sampled_rank = Vec::with_capacity(sampled.len());
+        let mut total = 0;
+        for word in &sampled {
+            sampled_rank.push(total);
+            total += word.count_ones();
+        }
+        FmIndex { codes: FmIndex::codes(&alphabet), alphabet, bwt, primary, counts, occ, sample_rate, sampled, sampled_rank, samples }
+    }
+
+    /// Length of the text
+    pub fn len(&self) -> usize {
+        self.bwt.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // number of the symbol `code` in bwt[0..row]
+    fn occ(&self, code: usize, row: usize) -> usize {
+        let block = row / BLOCK;
+        let mut res = self.occ[block * self.alphabet.len() + code] as usize;
+        for idx in block * BLOCK..row {
+            if self.bwt[idx] as usize == code && idx != self.primary {
+                res += 1;
+            }
+        }
+        res
+    }
+
+    // rows of the suffix array starting with the pattern
+    fn range(&self, pattern: &str) -> (usize, usize) {
+        let (mut lo, mut hi) = (0, self.bwt.len());
+        for &ch in pattern.as_bytes().iter().rev() {
+            let code = match self.codes[ch as usize] {
+                Some(code) => code as usize,
+                None => return (0, 0),
+            };
+            lo = self.counts[code] + self.occ(code, lo);
+            hi = self.counts[code] + self.occ(code, hi);
+            if lo >= hi {
+                return (0, 0);
+            }
+        }
+        (lo, hi)
+    }
+
+    /// Number of the occurrences of the pattern
+    pub fn count(&self, pattern: &str) -> usize {
+        let (lo, hi) = self.range(pattern);
+        hi - lo
+    }
+
+    pub fn contains(&self, pattern: &str) -> bool {
+        self.count(pattern) > 0
+    }
+
+    /// Sorted positions of the occurrences of the pattern
+    pub fn locate(&self, pattern: &str) -> Vec<usize> {
+        let (lo, hi) = self.range(pattern);
+        let mut res: Vec<usize> = (lo..hi).map(|row| self.position(row)).collect();
+        res.sort_unstable();
+        res
+    }
+
+    // start of the suffix in the row: walks back by LF-mapping to a sampled row
+    fn position(&self, mut row: usize) -> usize {
+        let mut steps = 0;
+        while self.sampled[row / 64] >> (row % 64) & 1 == 0 {
+            let code = self.bwt[row] as usize;
+            row = self.counts[code] + self.occ(code, row);
+            steps += 1;
+        }
+        let rank = self.sampled_rank[row / 64] as usize + (self.sampled[row / 64] & ((1u64 << (row % 64)) - 1)).count_ones() as usize;
+        self.samples[rank] as usize + steps
+    }
+
+    /// Serializes the index. The occurrence counts are not stored and are rebuilt by `from_bytes`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = MAGIC.to_vec();
+        for value in &[self.bwt.len(), self.primary, self.sample_rate, self.alphabet.len(), self.samples.len()] {
+            res.extend_from_slice(&(*value as u64).to_le_bytes());
+        }
+        res.extend_from_slice(&self.alphabet);
+        res.extend_from_slice(&self.bwt);
+        for word in &self.sampled {
+            res.extend_from_slice(&word.to_le_bytes());
+        }
+        for sample in &self.samples {
+            res.extend_from_slice(&sample.to_le_bytes());
+        }
+        res
+    }
+
+    /// Loads the index saved by `to_bytes`, `None` for malformed data
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+            if bytes.len() < len {
+                return None;
+            }
+            let (head, tail) = bytes.split_at(len);
+            *bytes = tail;
+            Some(head)
+        }
+        fn read_u64(bytes: &mut &[u8]) -> Option<u64> {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(take(bytes, 8)?);
+            Some(u64::from_le_bytes(buf))
+        }
+
+        let mut bytes = bytes;
+        if take(&mut bytes, 4)? != MAGIC {
+            return None;
+        }
+        let mut header = [0usize; 5];
+        for value in header.iter_mut() {
+            *value = read_u64(&mut bytes)? as usize;
+        }
+        let [len, primary, sample_rate, sigma, samples_len] = header;
+        if len == 0 || primary >= len || sample_rate == 0 || sigma > 256 || samples_len > len || len > bytes.len() {
+            return None;
+        }
+        let alphabet = take(&mut bytes, sigma)?.to_vec();
+        if alphabet.windows(2).any(|w| w[0] >= w[1]) {
+            return None;
+        }
+        let bwt = take(&mut bytes, len)?.to_vec();
+        if bwt.iter().enumerate().any(|(row, &code)| row != primary && code as usize >= sigma) {
+            return None;
+        }
+        let mut sampled = Vec::with_capacity(len / 64 + 1);
+        for _ in 0..len / 64 + 1 {
+            sampled.push(read_u64(&mut bytes)?);
+        }
+        if sampled.iter().map(|word| word.count_ones() as usize).sum::<usize>() != samples_len {
+            return None;
+        }
+        let mut samples = Vec::with_capacity(samples_len);
+        for chunk in take(&mut bytes, samples_len * 4)?.chunks(4) {
+            let sample = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            if sample as usize >= len {
+                return None;
+            }
+            samples.push(sample);
+        }
+        if !bytes.is_empty() || sampled[primary / 64] >> (primary % 64) & 1 == 0 {
+            return None;
+        }
+        let index = FmIndex::finish(alphabet, bwt, primary, sample_rate, sampled, samples);
+        if !index.is_single_cycle() {
+            return None;
+        }
+        Some(index)
+    }
+
+    // LF-mapping walks from the row of the empty suffix through every row to the primary one,
+    // otherwise `position` may never reach a sampled row
+    fn is_single_cycle(&self) -> bool {
+        let mut row = 0;
+        for _ in 1..self.bwt.len() {
+            if row == self.primary {
+                return false;
+            }
+            let code = self.bwt[row] as usize;
+            row = self.counts[code] + self.occ(code, row);
+        }
+        row == self.primary
+    }
+}
+
+#[test]
+fn test_bwt() {
+    for src in &["", "a", "banana", "abracadabra", "aaaa", "abab", "mississippi", "zyx\u{0}\u{1}"] {
+        let (last, row) = bwt(src);
+        assert_eq!(last.len(), src.len());
+        assert_eq!(inverse_bwt(&last, row), src.as_bytes().to_vec());
+    }
+    assert_eq!(bwt("abracadabra"), (b"rdarcaaaabb".to_vec(), 2));
+}
+
+#[test]
+fn test_fm_index() {
+    let text = "mississippi$mississippi and the river";
+    for rate in &[1, 3, 32] {
+        let index = FmIndex::with_sample_rate(text, *rate);
+        assert_eq!(index.len(), text.len());
+        for len in 1..5 {
+            for start in 0..=text.len() - len {
+                let pattern = &text[start..start + len];
+                assert_eq!(index.locate(pattern), crate::string::kmp(text, pattern));
+                assert_eq!(index.count(pattern), crate::string::kmp(text, pattern).len());
+            }
+        }
+        assert_eq!(index.count("ssissippi"), 2);
+        assert_eq!(index.count("x"), 0);
+        assert_eq!(index.count("sippix"), 0);
+        assert_eq!(index.locate(""), (0..=text.len()).collect::<Vec<usize>>());
+    }
+    let index = FmIndex::build("");
+    assert!(index.is_empty());
+    assert_eq!(index.count("a"), 0);
+}
+
+#[test]
+fn test_fm_index_bytes() {
+    let text: String = (0..500).map(|i| (b'a' + (i * i % 7) as u8) as char).collect();
+    let index = FmIndex::with_sample_rate(&text, 5);
+    let bytes = index.to_bytes();
+    let restored = FmIndex::from_bytes(&bytes).unwrap();
+    for pattern in &["abc", "a", "cfc", "gggg", "ebeb"] {
+        assert_eq!(restored.locate(pattern), index.locate(pattern));
+    }
+    assert!(FmIndex::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+    assert!(FmIndex::from_bytes(&bytes[1..]).is_none());
+    assert!(FmIndex::from_bytes(&[]).is_none());
+    // swapped symbols of the bwt split the LF-mapping into several cycles
+    let index = FmIndex::with_sample_rate("abcabcabcx", 1000);
+    let mut bytes = index.to_bytes();
+    let offset = 4 + 5 * 8 + index.alphabet.len();
+    let (i, j) = (0..index.bwt.len())
+        .flat_map(|i| (i + 1..index.bwt.len()).map(move |j| (i, j)))
+        .find(|&(i, j)| i != index.primary && j != index.primary && index.bwt[i] != index.bwt[j])
+        .unwrap();
+    bytes.swap(offset + i, offset + j);
+    assert!(FmIndex::from_bytes(&bytes).is_none());
+    let restored = FmIndex::from_bytes(&FmIndex::build("").to_bytes()).unwrap();
+    assert_eq!(restored.count(""), 1);
+}
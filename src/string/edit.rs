@@ -0,0 +1,293 @@
+use std::cmp::min;
+use std::collections::BTreeMap;
+
+/// Operation of an edit script transforming `first` into `second`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOp {
+    /// `first[i]` is kept, it equals `second[j]`
+    Match(usize, usize),
+    /// `first[i]` is replaced by `second[j]`
+    Replace(usize, usize),
+    /// `second[j]` is inserted
+    Insert(usize),
+    /// `first[i]` is deleted
+    Delete(usize),
+}
+
+/// Levenshtein distance with the edit script (the alignment of the strings), the positions are the indices of `char`s.
+/// Uses the full dynamic programming table: O(nm) time and memory.
+///```
+/// use librualg::string::{levenshtein_edit_script, EditOp};
+///
+/// let (cost, script) = levenshtein_edit_script("kitten", "sitting", 1, 1, 1);
+/// assert_eq!(cost, 3);
+/// assert_eq!(script, vec![
+///     EditOp::Replace(0, 0), EditOp::Match(1, 1), EditOp::Match(2, 2), EditOp::Match(3, 3),
+///     EditOp::Replace(4, 4), EditOp::Match(5, 5), EditOp::Insert(6)
+/// ]);
+/// ```
+pub fn levenshtein_edit_script(first: &str, second: &str, delete_cost: u32, insert_cost: u32, replace_cost: u32) -> (u32, Vec<EditOp>) {
+    let a: Vec<char> = first.chars().collect();
+    let b: Vec<char> = second.chars().collect();
+    levenshtein_edit_script_slice(&a, &b, delete_cost, insert_cost, replace_cost)
+}
+
+/// Levenshtein distance with the edit script over slices
+///```
+/// use librualg::string::{levenshtein_edit_script_slice, EditOp};
+///
+/// let (cost, script) = levenshtein_edit_script_slice(&["let", "x", "=", "1"], &["let", "y", "=", "1"], 1, 1, 1);
+/// assert_eq!(cost, 1);
+/// assert_eq!(script[1], EditOp::Replace(1, 1));
+/// ```
+pub fn levenshtein_edit_script_slice<T: Eq>(first: &[T], second: &[T], delete_cost: u32, insert_cost: u32, replace_cost: u32) -> (u32, Vec<EditOp>) {
+    let costs = Costs { delete: delete_cost, insert: insert_cost, replace: replace_cost };
+    edit_script(first, second, &costs, 0, 0)
+}
+
+/// Levenshtein distance with the edit script in linear memory (Hirschberg's algorithm),
+/// the positions are the indices of `char`s. O(nm) time, O(n + m) memory.
+///```
+/// use librualg::string::{hirschberg, levenshtein_distance, EditOp};
+///
+/// let (cost, script) = hirschberg("POLYNOMIAL", "EXPONENTIAL", 1, 1, 1);
+/// assert_eq!(cost, levenshtein_distance("POLYNOMIAL", "EXPONENTIAL", 1, 1, 1));
+/// assert_eq!(script.last(), Some(&EditOp::Match(9, 10)));
+/// assert_eq!(hirschberg("é", "è", 1, 1, 1), (1, vec![EditOp::Replace(0, 0)]));
+/// ```
+pub fn hirschberg(first: &str, second: &str, delete_cost: u32, insert_cost: u32, replace_cost: u32) -> (u32, Vec<EditOp>) {
+    let a: Vec<char> = first.chars().collect();
+    let b: Vec<char> = second.chars().collect();
+    hirschberg_slice(&a, &b, delete_cost, insert_cost, replace_cost)
+}
+
+/// Hirschberg's algorithm over slices
+///```
+/// use librualg::string::{hirschberg_slice, EditOp};
+///
+/// let (cost, script) = hirschberg_slice(&[1, 2, 3], &[1, 3], 1, 1, 1);
+/// assert_eq!(cost, 1);
+/// assert_eq!(script, vec![EditOp::Match(0, 0), EditOp::Delete(1), EditOp::Match(2, 1)]);
+/// ```
+pub fn hirschberg_slice<T: Eq>(first: &[T], second: &[T], delete_cost: u32, insert_cost: u32, replace_cost: u32) -> (u32, Vec<EditOp>) {
+    let costs = Costs { delete: delete_cost, insert: insert_cost, replace: replace_cost };
+    let mut script = vec![];
+    let cost = hirschberg_rec(first, second, &costs, 0, 0, &mut script);
+    (cost, script)
+}
+
+/// Damerau–Levenshtein distance: insertions, deletions, replacements and transpositions
+/// of adjacent `char`s, all with the cost 1 (substrings may be edited after a transposition)
+///```
+/// use librualg::string::damerau_levenshtein_distance;
+///
+/// assert_eq!(damerau_levenshtein_distance("ca", "abc"), 2);
+/// assert_eq!(damerau_levenshtein_distance("abcdef", "abdcef"), 1);
+/// assert_eq!(damerau_levenshtein_distance("", "abc"), 3);
+/// assert_eq!(damerau_levenshtein_distance("ёж", "жё"), 1);
+/// ```
+pub fn damerau_levenshtein_distance(first: &str, second: &str) -> u32 {
+    let a: Vec<char> = first.chars().collect();
+    let b: Vec<char> = second.chars().collect();
+    damerau_levenshtein_distance_slice(&a, &b)
+}
+
+/// Damerau–Levenshtein distance over slices (the elements must be ordered to remember their last positions)
+///```
+/// use librualg::string::damerau_levenshtein_distance_slice;
+///
+/// assert_eq!(damerau_levenshtein_distance_slice(&[1, 2, 3], &[2, 1, 3]), 1);
+/// ```
+pub fn damerau_levenshtein_distance_slice<T: Ord>(a: &[T], b: &[T]) -> u32 {
+    let inf = (a.len() + b.len()) as u32;
+    // d[i + 1][j + 1] is the distance between a[..i] and b[..j]
+    let mut d = vec![vec![0; b.len() + 2]; a.len() + 2];
+    d[0][0] = inf;
+    for i in 0..=a.len() {
+        d[i + 1][0] = inf;
+        d[i + 1][1] = i as u32;
+    }
+    for j in 0..=b.len() {
+        d[0][j + 1] = inf;
+        d[1][j + 1] = j as u32;
+    }
+    let mut last_row: BTreeMap<&T, usize> = BTreeMap::new();
+    for i in 1..=a.len() {
+        let mut last_col = 0;
+        for j in 1..=b.len() {
+            let k = *last_row.get(&b[j - 1]).unwrap_or(&0);
+            let l = last_col;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_col = j;
+                0
+            } else {
+                1
+            };
+            d[i + 1][j + 1] = min(
+                min(d[i][j] + cost, d[i + 1][j] + 1),
+                min(d[i][j + 1] + 1, d[k][l] + (i - k - 1) as u32 + 1 + (j - l - 1) as u32)
+            );
+        }
+        last_row.insert(&a[i - 1], i);
+    }
+    d[a.len() + 1][b.len() + 1]
+}
+
+struct Costs {
+    delete: u32,
+    insert: u32,
+    replace: u32,
+}
+
+fn edit_script<T: Eq>(a: &[T], b: &[T], costs: &Costs, offset_a: usize, offset_b: usize) -> (u32, Vec<EditOp>) {
+    let mut dp = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in 1..=a.len() {
+        dp[i][0] = dp[i - 1][0] + costs.delete;
+    }
+    for j in 1..=b.len() {
+        dp[0][j] = dp[0][j - 1] + costs.insert;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let diagonal = dp[i - 1][j - 1] + if a[i - 1] == b[j - 1] { 0 } else { costs.replace };
+            dp[i][j] = min(diagonal, min(dp[i - 1][j] + costs.delete, dp[i][j - 1] + costs.insert));
+        }
+    }
+    let mut script = vec![];
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] && dp[i][j] == dp[i - 1][j - 1] {
+            script.push(EditOp::Match(offset_a + i - 1, offset_b + j - 1));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && j > 0 && dp[i][j] == dp[i - 1][j - 1] + costs.replace {
+            script.push(EditOp::Replace(offset_a + i - 1, offset_b + j - 1));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && dp[i][j] == dp[i - 1][j] + costs.delete {
+            script.push(EditOp::Delete(offset_a + i - 1));
+            i -= 1;
+        } else {
+            script.push(EditOp::Insert(offset_b + j - 1));
+            j -= 1;
+        }
+    }
+    script.reverse();
+    (dp[a.len()][b.len()], script)
+}
+
+/// The last row of the table: the costs of transforming `a` into every prefix of `b`
+fn last_row<'a, T, A, B>(a: A, b: B, costs: &Costs) -> Vec<u32>
+    where T: Eq + 'a, A: Iterator<Item = &'a T>, B: Iterator<Item = &'a T> + ExactSizeIterator + Clone {
+    let len = b.len();
+    let mut row: Vec<u32> = (0..=len as u32).map(|j| j * costs.insert).collect();
+    let mut next = vec![0; len + 1];
+    for ch in a {
+        next[0] = row[0] + costs.delete;
+        for (j, other) in b.clone().enumerate() {
+            let diagonal = row[j] + if ch == other { 0 } else { costs.replace };
+            next[j + 1] = min(diagonal, min(row[j + 1] + costs.delete, next[j] + costs.insert));
+        }
+        std::mem::swap(&mut row, &mut next);
+    }
+    row
+}
+
+// the split of `b` for the middle of `a`, the rows are freed before the recursion
+fn split_point<T: Eq>(a: &[T], b: &[T], costs: &Costs) -> usize {
+    let mid = a.len() / 2;
+    let left = last_row(a[..mid].iter(), b.iter(), costs);
+    let right = last_row(a[mid..].iter().rev(), b.iter().rev(), costs);
+    let mut split = 0;
+    for k in 1..=b.len() {
+        if left[k] + right[b.len() - k] < left[split] + right[b.len() - split] {
+            split = k;
+        }
+    }
+    split
+}
+
+fn hirschberg_rec<T: Eq>(a: &[T], b: &[T], costs: &Costs, offset_a: usize, offset_b: usize, script: &mut Vec<EditOp>) -> u32 {
+    if a.len() <= 1 || b.len() <= 1 {
+        let (cost, ops) = edit_script(a, b, costs, offset_a, offset_b);
+        script.extend(ops);
+        return cost;
+    }
+    let mid = a.len() / 2;
+    let split = split_point(a, b, costs);
+    hirschberg_rec(&a[..mid], &b[..split], costs, offset_a, offset_b, script)
+        + hirschberg_rec(&a[mid..], &b[split..], costs, offset_a + mid, offset_b + split, script)
+}
+
+#[cfg(test)]
+fn apply_script(first: &str, second: &str, script: &[EditOp], costs: (u32, u32, u32)) -> (String, u32) {
+    let a: Vec<char> = first.chars().collect();
+    let b: Vec<char> = second.chars().collect();
+    let mut res = vec![];
+    let mut cost = 0;
+    let (mut i, mut j) = (0, 0);
+    for op in script {
+        match *op {
+            EditOp::Match(x, y) => {
+                assert!(x == i && y == j && a[x] == b[y]);
+                res.push(a[x]);
+                i += 1;
+                j += 1;
+            }
+            EditOp::Replace(x, y) => {
+                assert!(x == i && y == j && a[x] != b[y]);
+                res.push(b[y]);
+                cost += costs.2;
+                i += 1;
+                j += 1;
+            }
+            EditOp::Insert(y) => {
+                assert_eq!(y, j);
+                res.push(b[y]);
+                cost += costs.1;
+                j += 1;
+            }
+            EditOp::Delete(x) => {
+                assert_eq!(x, i);
+                cost += costs.0;
+                i += 1;
+            }
+        }
+    }
+    assert_eq!((i, j), (a.len(), b.len()));
+    (res.into_iter().collect(), cost)
+}
+
+#[test]
+fn test_edit_scripts() {
+    use crate::string::levenshtein_distance_slice;
+
+    let pairs = [("", ""), ("", "abc"), ("abc", ""), ("kitten", "sitting"), ("POLYNOMIAL", "EXPONENTIAL"),
+        ("abcdasdasd", "cddabcd"), ("intention", "execution"), ("aaaaaaaaaa", "a"), ("é", "è"), ("привет", "превед")];
+    for costs in &[(1, 1, 1), (1, 2, 3), (3, 1, 1), (2, 2, 5)] {
+        for (first, second) in &pairs {
+            let a: Vec<char> = first.chars().collect();
+            let b: Vec<char> = second.chars().collect();
+            let expected = levenshtein_distance_slice(&a, &b, costs.0, costs.1, costs.2);
+            let (cost, script) = levenshtein_edit_script(first, second, costs.0, costs.1, costs.2);
+            assert_eq!(cost, expected);
+            assert_eq!(apply_script(first, second, &script, *costs), (second.to_string(), expected));
+            let (cost, script) = hirschberg(first, second, costs.0, costs.1, costs.2);
+            assert_eq!(cost, expected);
+            assert_eq!(apply_script(first, second, &script, *costs), (second.to_string(), expected));
+        }
+    }
+}
+
+#[test]
+fn test_damerau_levenshtein_distance() {
+    assert_eq!(damerau_levenshtein_distance("", ""), 0);
+    assert_eq!(damerau_levenshtein_distance("abc", "abc"), 0);
+    assert_eq!(damerau_levenshtein_distance("ab", "ba"), 1);
+    assert_eq!(damerau_levenshtein_distance("ca", "abc"), 2);
+    assert_eq!(damerau_levenshtein_distance("a cat", "an act"), 2);
+    assert_eq!(damerau_levenshtein_distance("kitten", "sitting"), 3);
+    assert_eq!(damerau_levenshtein_distance("abc", ""), 3);
+    assert_eq!(damerau_levenshtein_distance("é", "è"), 1);
+    assert_eq!(damerau_levenshtein_distance_slice(&["b", "a"], &["a", "b", "c"]), 2);
+}
@@ -0,0 +1,276 @@
+#[derive(Clone, Debug)]
+enum Token {
+    Char(char),
+    // `?`
+    Any,
+    // `*`, does not cross `/`
+    Star,
+    // `**`, crosses `/`
+    DoubleStar,
+    // `**/`, zero or more directories: the state between the directories
+    Directories,
+    // the state inside a directory name of `**/`
+    DirectoryName,
+    Class { negated: bool, ranges: Vec<(char, char)> },
+}
+
+/// Compiled glob pattern.
+/// - `?` matches any character except `/`
+/// - `*` matches any sequence of characters except `/`
+/// - `**` matches any sequence of characters, `**/` matches zero or more directories
+/// - `[abc]`, `[a-z]` match a character from the class, `[!a-z]` or `[^a-z]` a character outside of it (never `/`)
+/// - `\` escapes the next character
+///
+/// The matching simulates the pattern automaton on the set of its states,
+/// it takes O(|pattern| * |text|) without backtracking.
+///```
+/// use librualg::string::Glob;
+///
+/// let glob = Glob::new("src/**/*.rs");
+/// assert!(glob.is_match("src/main.rs"));
+/// assert!(glob.is_match("src/string/glob.rs"));
+/// assert!(!glob.is_match("src/string/glob.rs.bak"));
+/// assert!(!glob.is_match("tests/string.rs"));
+///
+/// let glob = Glob::with_options("*.[ch]", true);
+/// assert!(glob.is_match("MAIN.C"));
+/// assert!(!glob.is_match("include/main.h"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Glob {
+    tokens: Vec<Token>,
+    case_insensitive: bool,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Self {
+        Glob::with_options(pattern, false)
+    }
+
+    pub fn with_options(pattern: &str, case_insensitive: bool) -> Self {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut tokens = vec![];
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '?' => tokens.push(Token::Any),
+                '*' => {
+                    if chars.get(i + 1) == Some(&'*') {
+                        i += 1;
+                        while chars.get(i + 1) == Some(&'*') {
+                            i += 1;
+                        }
+                        if chars.get(i + 1) == Some(&'/') {
+                            i += 1;
+                            tokens.push(Token::Directories);
+                            tokens.push(Token::DirectoryName);
+                        } else {
+                            tokens.push(Token::DoubleStar);
+                        }
+                    } else {
+                        tokens.push(Token::Star);
+                    }
+                }
+                '[' => match Glob::parse_class(&chars[i + 1..]) {
+                    Some((token, len)) => {
+                        tokens.push(token);
+                        i += len;
+                    }
+                    None => tokens.push(Token::Char('[')),
+                },
+                '\\' if i + 1 < chars.len() => {
+                    i += 1;
+                    tokens.push(Token::Char(chars[i]));
+                }
+                ch => tokens.push(Token::Char(ch)),
+            }
+            i += 1;
+        }
+        if case_insensitive {
+            for token in tokens.iter_mut() {
+                if let Token::Char(ch) = token {
+                    *ch = fold(*ch);
+                }
+            }
+        }
+        Glob { tokens, case_insensitive }
+    }
+
+    // class after `[`, returns the token and the number of the used characters, None if `]` is missing
+    fn parse_class(chars: &[char]) -> Option<(Token, usize)> {
+        let mut i = 0;
+        let negated = matches!(chars.first(), Some('!') | Some('^'));
+        if negated {
+            i += 1;
+        }
+        let mut ranges = vec![];
+        let mut first = true;
+        while i < chars.len() {
+            let mut ch = chars[i];
+            if ch == ']' && !first {
+                return Some((Token::Class { negated, ranges }, i + 1));
+            }
+            first = false;
+            if ch == '\\' && i + 1 < chars.len() {
+                i += 1;
+                ch = chars[i];
+            }
+            if i + 2 < chars.len() && chars[i + 1] == '-' && chars[i + 2] != ']' {
+                ranges.push((ch, chars[i + 2]));
+                i += 3;
+            } else {
+                ranges.push((ch, ch));
+                i += 1;
+            }
+        }
+        None
+    }
+
+    fn matches_char(&self, token: &Token, ch: char) -> bool {
+        match token {
+            Token::Char(expected) => *expected == if self.case_insensitive { fold(ch) } else { ch },
+            Token::Any => ch != '/',
+            Token::Class { negated, ranges } => {
+                if ch == '/' {
+                    return false;
+                }
+                let inside = |ch: char| ranges.iter().any(|&(from, to)| from <= ch && ch <= to);
+                let found = if self.case_insensitive {
+                    inside(ch) || inside(fold(ch)) || ch.to_uppercase().any(inside)
+                } else {
+                    inside(ch)
+                };
+                found != *negated
+            }
+            _ => false,
+        }
+    }
+
+    // adds the states reachable without reading a character
+    fn close(&self, states: &mut [bool]) {
+        for i in 0..self.tokens.len() {
+            if states[i] {
+                match self.tokens[i] {
+                    Token::Star | Token::DoubleStar => states[i + 1] = true,
+                    Token::Directories => states[i + 2] = true,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        let n = self.tokens.len();
+        let mut cur = vec![false; n + 1];
+        let mut next = vec![false; n + 1];
+        cur[0] = true;
+        self.close(&mut cur);
+        for ch in text.chars() {
+            for state in next.iter_mut() {
+                *state = false;
+            }
+            let mut alive = false;
+            for (i, token) in self.tokens.iter().enumerate() {
+                if !cur[i] {
+                    continue;
+                }
+                match token {
+                    Token::Star => next[i] |= ch != '/',
+                    Token::DoubleStar => next[i] = true,
+                    Token::Directories | Token::DirectoryName => {
+                        let boundary = if let Token::Directories = token { i } else { i - 1 };
+                        if ch == '/' {
+                            next[boundary] = true;
+                        } else {
+                            next[boundary + 1] = true;
+                        }
+                    }
+                    _ => next[i + 1] |= self.matches_char(token, ch),
+                }
+                alive = true;
+            }
+            if !alive {
+                return false;
+            }
+            self.close(&mut next);
+            std::mem::swap(&mut cur, &mut next);
+        }
+        cur[n]
+    }
+}
+
+fn fold(ch: char) -> char {
+    let mut lower = ch.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(single), None) => single,
+        _ => ch,
+    }
+}
+
+/// Matches the text with the glob pattern (see `Glob`)
+///```
+/// use librualg::string::wildmatch;
+///
+/// assert!(wildmatch("*.txt", "notes.txt"));
+/// assert!(!wildmatch("*.txt", "docs/notes.txt"));
+/// assert!(wildmatch("**/*.txt", "docs/notes.txt"));
+/// assert!(wildmatch("report-202[0-4]-??.csv", "report-2023-q1.csv"));
+/// ```
+pub fn wildmatch(pattern: &str, text: &str) -> bool {
+    Glob::new(pattern).is_match(text)
+}
+
+#[test]
+fn test_wildmatch() {
+    let cases = [
+        ("", "", true),
+        ("", "a", false),
+        ("*", "", true),
+        ("*", "abc", true),
+        ("*", "a/b", false),
+        ("**", "a/b", true),
+        ("a?c", "abc", true),
+        ("a?c", "a/c", false),
+        ("a*b*c", "aXXbYYc", true),
+        ("a*b*c", "aXXbYY", false),
+        ("a/**/b", "a/b", true),
+        ("a/**/b", "a/x/y/b", true),
+        ("a/**/b", "a/xb", false),
+        ("**/b", "b", true),
+        ("**/b", "x/y/b", true),
+        ("a/**", "a/x/y", true),
+        ("[abc]", "b", true),
+        ("[!abc]", "b", false),
+        ("[^abc]", "d", true),
+        ("[a-c]x", "cx", true),
+        ("[a-c]x", "dx", false),
+        ("[]]", "]", true),
+        ("[a-]", "-", true),
+        ("[!a]", "/", false),
+        ("[abc", "[abc", true),
+        ("\\*", "*", true),
+        ("\\*", "a", false),
+        ("ä*ö", "äbcö", true),
+    ];
+    for (pattern, text, expected) in cases.iter() {
+        assert_eq!(wildmatch(pattern, text), *expected, "{} {}", pattern, text);
+    }
+}
+
+#[test]
+fn test_glob_case_insensitive() {
+    let glob = Glob::with_options("READ[m-z]E.*", true);
+    assert!(glob.is_match("readme.md"));
+    assert!(glob.is_match("ReadMe.TXT"));
+    assert!(!glob.is_match("readle.md"));
+    assert!(Glob::with_options("ÄB", true).is_match("äb"));
+    assert!(!Glob::new("README.*").is_match("readme.md"));
+}
+
+#[test]
+fn test_glob_no_backtracking() {
+    let pattern = "a*".repeat(30) + "b";
+    let text = "a".repeat(200);
+    assert!(!wildmatch(&pattern, &text));
+    assert!(wildmatch(&pattern, &(text + "b")));
+}
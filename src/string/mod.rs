@@ -1,9 +1,45 @@
 use std::cmp::{min, max};
 use crate::segment_tree::{RmqMin, SegmentTreeMin, SegmentTreeMax};
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::BTreeMap;
+
+mod aho_corasick;
+mod approximate;
+mod alignment;
+mod boyer_moore;
+mod bwt;
+mod edit;
+mod glob;
+mod palindrome;
+mod periodicity;
+mod position;
+mod regex;
+mod rolling_hash;
+mod sais;
+mod similarity;
+mod subsequence;
+mod suffix_automaton;
+mod suffix_tree;
+
+pub use aho_corasick::{AhoCorasick, MatchKind, Match, StreamSearcher};
+pub use approximate::approximate_search;
+pub use alignment::{SubstitutionMatrix, GapPenalty, Alignment, needleman_wunsch, smith_waterman};
+pub use boyer_moore::Searcher;
+pub use bwt::{bwt, inverse_bwt, FmIndex};
+pub use edit::{EditOp, levenshtein_edit_script, levenshtein_edit_script_slice, hirschberg, hirschberg_slice, damerau_levenshtein_distance, damerau_levenshtein_distance_slice};
+pub use glob::{Glob, wildmatch};
+pub use palindrome::{manacher, manacher_slice, longest_palindrome, PalindromicTree};
+pub use periodicity::{lyndon_factorization, lyndon_factorization_slice, minimal_rotation, minimal_rotation_slice, borders, borders_slice, periods, periods_slice, Run, runs, runs_slice};
+pub use position::{TextPosition, text_positions, substring_chars, kmp_chars, z_function_chars};
+pub use regex::{Regex, RegexError, Captures};
+pub use rolling_hash::{RollingHash, rabin_karp};
+pub use sais::{suffix_array_sais, lcp_array, LcpTable};
+pub use similarity::{jaro, jaro_winkler, ngram_jaccard, ngram_dice, cosine_similarity, lcs_ratio};
+pub use subsequence::{lcs, lcs_slice, lcs_length, hunt_szymanski, lis, lds};
+pub use suffix_automaton::SuffixAutomaton;
+pub use suffix_tree::SuffixTree;
 
 /// Knuth–Morris–Pratt string-searching algorithm (or KMP algorithm).
-/// Return all occurrences of a substring.
+/// Return all occurrences of a substring as byte offsets (see `kmp_chars` for the char offsets).
 ///```
 /// use librualg::string::kmp;
 ///
@@ -11,15 +47,31 @@ use std::collections::{BTreeMap, VecDeque};
 /// ```
 
 pub fn kmp(t: &str, p: &str) -> Vec<usize> {
+    kmp_slice(t.as_bytes(), p.as_bytes())
+}
+
+/// Knuth–Morris–Pratt algorithm over slices (tokens, `char`s, ids).
+/// Return all occurrences of a subsequence of consecutive elements.
+///```
+/// use librualg::string::kmp_slice;
+///
+/// let text: Vec<char> = "día de días".chars().collect();
+/// let pattern: Vec<char> = "día".chars().collect();
+/// assert_eq!(kmp_slice(&text, &pattern), vec![0, 7]);
+/// assert_eq!(kmp_slice(&[1, 2, 1, 2, 1], &[1, 2, 1]), vec![0, 2]);
+/// ```
+pub fn kmp_slice<T: Eq>(t: &[T], p: &[T]) -> Vec<usize> {
+    if p.is_empty() {
+        return (0..=t.len()).collect();
+    }
     let mut res = vec![];
-    let pr = prefix_function(p);
+    let pr = prefix_function_slice(p);
     let mut idx = 0;
-    let pattern = p.as_bytes();
-    for (i, value) in t.as_bytes().iter().enumerate() {
-        while idx > 0  && pattern[idx] != *value{
+    for (i, value) in t.iter().enumerate() {
+        while idx > 0  && p[idx] != *value{
             idx = pr[idx - 1];
         }
-        if pattern[idx] == *value {
+        if p[idx] == *value {
             idx += 1;
         }
         if idx == p.len() {
@@ -48,14 +100,28 @@ fn test_kmp(){
 /// ```
 
 pub fn kmp_first(t: &str, p: &str) -> Option<usize> {
-    let pr = prefix_function(p);
+    kmp_first_slice(t.as_bytes(), p.as_bytes())
+}
+
+/// Knuth–Morris–Pratt algorithm over slices.
+/// Return first occurrence of a subsequence of consecutive elements.
+///```
+/// use librualg::string::kmp_first_slice;
+///
+/// assert_eq!(kmp_first_slice(&["GET", "/", "HTTP"], &["/", "HTTP"]), Some(1));
+/// assert_eq!(kmp_first_slice(&[1, 2, 3], &[3, 2]), None);
+/// ```
+pub fn kmp_first_slice<T: Eq>(t: &[T], p: &[T]) -> Option<usize> {
+    if p.is_empty() {
+        return Some(0);
+    }
+    let pr = prefix_function_slice(p);
     let mut idx = 0;
-    let pattern = p.as_bytes();
-    for (i, value) in t.as_bytes().iter().enumerate() {
-        while idx > 0  && pattern[idx] != *value{
+    for (i, value) in t.iter().enumerate() {
+        while idx > 0  && p[idx] != *value{
             idx = pr[idx - 1];
         }
-        if pattern[idx] == *value {
+        if p[idx] == *value {
             idx += 1;
         }
         if idx == p.len() {
@@ -67,6 +133,17 @@ pub fn kmp_first(t: &str, p: &str) -> Option<usize> {
 
 
 
+#[test]
+fn test_kmp_slice() {
+    let tokens = ["if", "(", "x", ")", "{", "if", "(", "x", ")", "}"];
+    assert_eq!(kmp_slice(&tokens, &["if", "(", "x", ")"]), vec![0, 5]);
+    assert_eq!(kmp_first_slice(&tokens, &["(", "x", ")", "}"]), Some(6));
+    assert_eq!(kmp_slice(&[1u32, 1, 1], &[]), vec![0, 1, 2, 3]);
+    assert_eq!(kmp_first_slice(&[1u32, 1, 1], &[2]), None);
+    let text: Vec<char> = "日本語の日本".chars().collect();
+    assert_eq!(kmp_slice(&text, &['日', '本']), vec![0, 4]);
+}
+
 #[test]
 fn test_kmp_first(){
     assert_eq!(kmp_first("ababcxabdabcxabcxabcde", "abcxabcde"), Some(13));
@@ -76,6 +153,7 @@ fn test_kmp_first(){
 }
 
 /// Levenshtein distance (Metric of the difference between two symbol sequences).
+/// The costs are the costs of the operations transforming `first` into `second`.
 ///```
 /// use librualg::string::levenshtein_distance;
 ///
@@ -83,18 +161,31 @@ fn test_kmp_first(){
 /// assert_eq!(levenshtein_distance("aaa", "aaa", 1, 1, 1), 0);
 /// ```
 pub fn levenshtein_distance(first: &str, second: &str, delete_cost: u32, insert_cost: u32, replace_cost: u32) -> u32 {
-    let first = first.as_bytes();
-    let second = second.as_bytes();
+    levenshtein_distance_slice(first.as_bytes(), second.as_bytes(), delete_cost, insert_cost, replace_cost)
+}
+
+/// Levenshtein distance over slices
+///```
+/// use librualg::string::levenshtein_distance_slice;
+///
+/// let first = ["the", "quick", "brown", "fox"];
+/// let second = ["the", "brown", "dog"];
+/// assert_eq!(levenshtein_distance_slice(&first, &second, 1, 1, 1), 2);
+/// let first: Vec<char> = "żółw".chars().collect();
+/// let second: Vec<char> = "zółw".chars().collect();
+/// assert_eq!(levenshtein_distance_slice(&first, &second, 1, 1, 1), 1);
+/// ```
+pub fn levenshtein_distance_slice<T: Eq>(first: &[T], second: &[T], delete_cost: u32, insert_cost: u32, replace_cost: u32) -> u32 {
     let mut dist_old = vec![0; first.len() + 1];
     for j in 1..(first.len() + 1) {
-        dist_old[j] = dist_old[j - 1] + insert_cost;
+        dist_old[j] = dist_old[j - 1] + delete_cost;
     }
     for i in 1..second.len() + 1 {
         let mut dist = vec![0; first.len() + 1];
-        dist[0] = dist_old[0] + delete_cost;
+        dist[0] = dist_old[0] + insert_cost;
         for j in 1..(first.len() + 1) {
             if second[i - 1] != first[j - 1] {
-                dist[j] = min(min(dist_old[j] + delete_cost, dist_old[j - 1] + insert_cost), dist[j - 1] + replace_cost);
+                dist[j] = min(min(dist_old[j] + insert_cost, dist_old[j - 1] + replace_cost), dist[j - 1] + delete_cost);
             } else {
                 dist[j] = dist_old[j - 1];
             }
@@ -113,6 +204,15 @@ fn test_levenshtein_distance(){
     assert_eq!(levenshtein_distance("", "aaa", 1, 1, 1), 3);
 }
 
+#[test]
+fn test_levenshtein_distance_costs() {
+    assert_eq!(levenshtein_distance("", "aaa", 5, 1, 7), 3);
+    assert_eq!(levenshtein_distance("aaa", "", 5, 1, 7), 15);
+    assert_eq!(levenshtein_distance("abc", "ab", 1, 10, 10), 1);
+    assert_eq!(levenshtein_distance("abc", "abd", 1, 1, 7), 2);
+    assert_eq!(levenshtein_distance("abc", "abd", 5, 5, 7), 7);
+}
+
 /// Search for the minimum string period
 ///```
 /// use librualg::string::minimum_string_period;
@@ -122,17 +222,26 @@ fn test_levenshtein_distance(){
 /// ```
 
 pub fn minimum_string_period(src: &str) ->&str {
-    for (idx, value) in z_function(src).iter().enumerate() {
-        if value + idx == src.len() && src.len() % idx == 0 {
-            return &src[..idx];
-        } else if value + idx == src.len() {
-            let k = src.len() % idx;
-            if src[..k] == src[src.len() - k..] {
-                return &src[..idx];
-            }
+    let chars: Vec<char> = src.chars().collect();
+    let period = minimum_period_slice(&chars);
+    &src[..chars[..period].iter().map(|ch| ch.len_utf8()).sum()]
+}
+
+/// Length of the minimum period of a slice: the smallest `p` with `src[i] == src[i + p]`
+///```
+/// use librualg::string::minimum_period_slice;
+///
+/// assert_eq!(minimum_period_slice(&[1, 2, 1, 2, 1]), 2);
+/// assert_eq!(minimum_period_slice(&[1, 2, 3]), 3);
+/// assert_eq!(minimum_period_slice::<u8>(&[]), 0);
+/// ```
+pub fn minimum_period_slice<T: Eq>(src: &[T]) -> usize {
+    for (idx, value) in z_function_slice(src).iter().enumerate().skip(1) {
+        if value + idx == src.len() {
+            return idx;
         }
     }
-    src
+    src.len()
 }
 
 #[test]
@@ -141,6 +250,9 @@ fn test_minimum_string_period() {
     assert_eq!(minimum_string_period("abcdefg"), "abcdefg");
     assert_eq!(minimum_string_period("abcabcabcd"), "abcabcabcd");
     assert_eq!(minimum_string_period(""), "");
+    assert_eq!(minimum_string_period("дадада"), "да");
+    assert_eq!(minimum_string_period("日本日本日"), "日本");
+    assert_eq!(minimum_string_period("é"), "é");
 }
 
 /// Search for distinct substring
@@ -154,12 +266,15 @@ fn test_minimum_string_period() {
 /// assert_eq!(values, vec!["a", "aa", "aab", "aaba", "ab", "aba", "abaa", "abaab", "abaaba", "b", "ba", "baa", "baab", "baaba"]);
 /// ```
 pub fn distinct_substrings(s: &str)->Vec<&str> {
+    let chars: Vec<char> = s.chars().collect();
+    let offsets: Vec<usize> = s.char_indices().map(|(offset, _)| offset).chain(Some(s.len())).collect();
+    let n = chars.len();
     let mut seq = vec![];
-    for i in (0..s.len()).rev() {
-        let pr = z_function(&s[i ..s.len()]);
-        let res = s.len() - i - pr.iter().max_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
+    for i in (0..n).rev() {
+        let pr = z_function_slice(&chars[i..]);
+        let res = n - i - pr.iter().max().unwrap();
         for j in 0..res {
-            seq.push(&s[i..s.len() - j]);
+            seq.push(&s[offsets[i]..offsets[n - j]]);
         }
     }
     seq
@@ -174,11 +289,13 @@ fn test_distinct_substrings(){
     values.sort();
     assert_eq!(values, vec!["a", "aa", "aab", "aaba", "ab", "aba", "abaa", "abaab", "abaaba", "b", "ba", "baa", "baab", "baaba"]);
     assert_eq!(distinct_substrings("abacabadabacaba").len(), 85);
+    let mut values = distinct_substrings("ёжё");
+    values.sort();
+    assert_eq!(values, vec!["ж", "жё", "ё", "ёж", "ёжё"]);
 }
 
-fn prefix_function(src: &str) -> Vec<usize> {
-    let mut pi = vec![0; src.len()];
-    let arr = src.as_bytes();
+fn prefix_function_slice<T: Eq>(arr: &[T]) -> Vec<usize> {
+    let mut pi = vec![0; arr.len()];
     for i in 1 .. arr.len() {
         let mut j = pi[i - 1];
         while j > 0 && arr[i] != arr[j] {
@@ -194,19 +311,34 @@ fn prefix_function(src: &str) -> Vec<usize> {
 
 #[test]
 fn test_prefix_function() {
-    assert_eq!(prefix_function("abacaba"), [0, 0, 1, 0, 1, 2, 3]);
-    assert_eq!(prefix_function("b"), [0]);
-    assert_eq!(prefix_function("aaaaa"), [0, 1, 2, 3, 4]);
-    assert_eq!(prefix_function(""), []);
+    assert_eq!(prefix_function_slice(b"abacaba"), [0, 0, 1, 0, 1, 2, 3]);
+    assert_eq!(prefix_function_slice(b"b"), [0]);
+    assert_eq!(prefix_function_slice(b"aaaaa"), [0, 1, 2, 3, 4]);
+    assert_eq!(prefix_function_slice(b""), []);
 }
 
+/// Z-function over the bytes of the string (see `z_function_chars` for the chars)
+///```
+/// use librualg::string::z_function;
+///
+/// assert_eq!(z_function("abacaba"), vec![0, 0, 1, 0, 3, 0, 1]);
+/// ```
 pub fn z_function(src: &str) -> Vec<usize> {
-    let mut z = vec![0; src.len()];
+    z_function_slice(src.as_bytes())
+}
+
+/// Z-function over slices
+///```
+/// use librualg::string::z_function_slice;
+///
+/// assert_eq!(z_function_slice(&[7, 7, 1, 7, 7]), vec![0, 1, 0, 2, 1]);
+/// ```
+pub fn z_function_slice<T: Eq>(arr: &[T]) -> Vec<usize> {
+    let mut z = vec![0; arr.len()];
     let mut l = 0;
     let mut r = 0;
 
-    let arr = src.as_bytes();
-    for i in 1..src.len() {
+    for i in 1..arr.len() {
         if i <= r {
             z[i] = min(r - i + 1, z[i - l]);
         }
@@ -221,6 +353,17 @@ pub fn z_function(src: &str) -> Vec<usize> {
     z
 }
 
+#[test]
+fn test_slice_periods() {
+    assert_eq!(z_function_slice(&['a', 'b', 'a', 'c', 'a', 'b', 'a']), z_function("abacaba"));
+    assert_eq!(minimum_period_slice(&["ab", "cd", "ab", "cd", "ab"]), 2);
+    assert_eq!(minimum_period_slice(&[5]), 1);
+    let text: Vec<char> = "ёжёжё".chars().collect();
+    assert_eq!(minimum_period_slice(&text), 2);
+    assert_eq!(levenshtein_distance_slice(&text, &['ж', 'ё'], 1, 1, 1), 3);
+    assert_eq!(levenshtein_distance_slice::<u32>(&[], &[1, 2], 1, 1, 1), 2);
+}
+
 #[test]
 fn test_z_function_ascii() {
     assert_eq!(z_function("abacaba"), [0, 0, 1, 0, 3, 0, 1]);
@@ -234,6 +377,16 @@ fn test_z_function_ascii() {
 /// assert_eq!(suffix_array("bababa$").0, vec![6, 5, 3, 1, 4, 2, 0]);
 /// ```
 pub fn suffix_array(src: &str) -> (Vec<usize>, Vec<usize>) {
+    suffix_array_slice(src.as_bytes())
+}
+
+/// Sufix Array over slices (the suffixes are compared cyclically, like in `suffix_array`)
+///```
+/// use librualg::string::suffix_array_slice;
+///
+/// assert_eq!(suffix_array_slice(&[2, 1, 2, 1, 0]).0, vec![4, 3, 1, 2, 0]);
+/// ```
+pub fn suffix_array_slice<T: Ord>(src: &[T]) -> (Vec<usize>, Vec<usize>) {
 
     use std::cmp::Ordering;
 
@@ -309,26 +462,18 @@ pub fn suffix_array(src: &str) -> (Vec<usize>, Vec<usize>) {
         arr
     }
 
-    let bytes = src.as_bytes();
     let length = src.len();
-    let mut suffix_array = vec![0; length];
+    if length == 0 {
+        return (vec![], vec![]);
+    }
+    let mut suffix_array: Vec<usize> = (0..length).collect();
     let mut classes = vec![0; length];
-    {
-        let mut a: Vec<Pair<u8>> = vec![Default::default(); length];
-        for (i, ch) in bytes.iter().enumerate() {
-            a[i] = Pair{first: *ch, second: i};
-        }
-        a.sort();
-        for i in 0..length {
-            suffix_array[i] = a[i].second;
-        }
-        classes[suffix_array[0]] = 0;
-        for i in 1..length {
-            if a[i].first == a[i - 1].first {
-                classes[suffix_array[i]] = classes[suffix_array[i - 1]];
-            }else {
-                classes[suffix_array[i]] = classes[suffix_array[i - 1]] + 1;
-            }
+    suffix_array.sort_by(|a, b| src[*a].cmp(&src[*b]).then(a.cmp(b)));
+    for i in 1..length {
+        if src[suffix_array[i]] == src[suffix_array[i - 1]] {
+            classes[suffix_array[i]] = classes[suffix_array[i - 1]];
+        }else {
+            classes[suffix_array[i]] = classes[suffix_array[i - 1]] + 1;
         }
     }
     let mut k = 0;
@@ -358,6 +503,10 @@ pub fn suffix_array(src: &str) -> (Vec<usize>, Vec<usize>) {
 fn test_suffix_array() {
     assert_eq!(suffix_array("ababba$").0, vec![6, 5, 0, 2, 4, 1, 3]);
     assert_eq!(suffix_array("bababa$").0, vec![6, 5, 3, 1, 4, 2, 0]);
+    assert_eq!(suffix_array("").0, vec![]);
+    let chars: Vec<char> = "ababba$".chars().collect();
+    assert_eq!(suffix_array_slice(&chars), suffix_array("ababba$"));
+    assert_eq!(suffix_array_slice(&[3u32, 1, 3, 1, 0]).0, vec![4, 3, 1, 2, 0]);
 }
 
 /// Longest Common Prefix
@@ -469,88 +618,42 @@ fn test_hash() {
 /// assert_eq!(common_substring("aba", "cabdd"), Some("ab"));
 /// ```
 
-#[allow(clippy::many_single_char_names)]
 pub fn common_substring<'a> (a: &'a str, b: &'a str) -> Option<&'a str> {
-    if a.is_empty() || b.is_empty() {
-        return None;
-    }
-    let mut p: Vec<u64> = vec![1; max(a.len(), b.len())];
-    let mut h1: Vec<u64> = vec![0; a.len()];
-    let mut h2: Vec<u64> = vec![0; b.len()];
-    for idx in 1..p.len() {
-        p[idx] = p[idx - 1].wrapping_mul(31);
-    }
-    for (idx, ch) in a.as_bytes().iter().enumerate() {
-        h1[idx] = (*ch as u64).wrapping_mul(p[idx]);
-        if idx != 0 {
-            h1[idx] = h1[idx].wrapping_add(h1[idx - 1]);
-        }
-    }
-    for (idx, ch) in b.as_bytes().iter().enumerate() {
-        h2[idx] = (*ch as u64).wrapping_mul(p[idx]);
-        if idx != 0 {
-            h2[idx] = h2[idx].wrapping_add(h2[idx - 1]);
-        }
-    }
-    let mut res = None;
+    let ha = RollingHash::build(a);
+    let hb = RollingHash::build_with_bases(b, ha.bases());
+    common_substring_hashed(a, b, &ha, &hb)
+}
+
+fn common_substring_hashed<'a>(a: &'a str, b: &'a str, ha: &RollingHash, hb: &RollingHash) -> Option<&'a str> {
+    // the substrings are hashed by bytes, but start and end at the boundaries of chars
+    let offsets_a: Vec<usize> = a.char_indices().map(|(offset, _)| offset).chain(Some(a.len())).collect();
+    let offsets_b: Vec<usize> = b.char_indices().map(|(offset, _)| offset).chain(Some(b.len())).collect();
+    // byte range in `b` of a common substring of the given number of chars
+    let find = |len: usize| -> Option<(usize, usize)> {
+        // all the windows of `a` with the hash: a collision must not hide a real match
+        let mut map: BTreeMap<(u64, u64), Vec<(usize, usize)>> = BTreeMap::new();
+        for w in offsets_a.windows(len + 1) {
+            map.entry(ha.substring_hash(w[0], w[len])).or_default().push((w[0], w[len]));
+        }
+        offsets_b.windows(len + 1).map(|w| (w[0], w[len])).find(|&(l, r)| match map.get(&hb.substring_hash(l, r)) {
+            Some(windows) => windows.iter().any(|&(j, k)| a.as_bytes()[j..k] == b.as_bytes()[l..r]),
+            None => false,
+        })
+    };
     let mut l = 0;
-    let mut r = min(a.len(), b.len()) - 1;
+    let mut r = min(offsets_a.len(), offsets_b.len()) - 1;
     while l < r {
         let mid = r - (r - l) / 2;
-        let mut map = BTreeMap::new();
-        for i in 0..a.len() - mid + 1 {
-            let mut hash = h1[i + mid - 1];
-            if i != 0 {
-                hash = hash.wrapping_sub(h1[i - 1]);
-            }
-            hash = hash.wrapping_mul(p[p.len() - i - 1]);
-            map.insert(hash, i);
-        }
-        let mut f = false;
-        for i in 0..b.len() - mid + 1 {
-            let mut hash = h2[i + mid - 1];
-            if i != 0 {
-                hash = hash.wrapping_sub(h2[i - 1]);
-            }
-            hash = hash.wrapping_mul(p[p.len() - i - 1]);
-            if let Some(idx) = map.get(&hash) {
-                if b[i..i + mid] == a[*idx..*idx + mid] {
-                    res = Some(&b[i..i + mid]);
-                    f = true;
-                    break;
-                }
-            }
-        }
-        if f {
-            l = mid + 1;
+        if find(mid).is_some() {
+            l = mid;
         } else {
             r = mid - 1;
         }
     }
-
-    let mut map = BTreeMap::new();
-    for i in 0..a.len() - l + 1 {
-        let mut hash = h1[i + l - 1];
-        if i != 0 {
-            hash = hash.wrapping_sub(h1[i - 1]);
-        }
-        hash = hash.wrapping_mul(p[p.len() - i - 1]);
-        map.insert(hash, i);
-    }
-    for i in 0..b.len() - l + 1 {
-        let mut hash = h2[i + l - 1];
-        if i != 0 {
-            hash = hash.wrapping_sub(h2[i - 1]);
-        }
-        hash = hash.wrapping_mul(p[p.len() - i - 1]);
-        if let Some(idx) = map.get(&hash) {
-            if b[i..i + l] == a[*idx..*idx + l] {
-                res = Some(&b[i..i + l]);
-                break;
-            }
-        }
+    if l == 0 {
+        return None;
     }
-    res
+    find(l).map(|(start, end)| &b[start..end])
 }
 
 #[test]
@@ -559,45 +662,25 @@ fn test_common_substring() {
     assert_eq!(common_substring("aba", "cabdd"), Some("ab"));
     assert_eq!(common_substring("aaaaa", "bbaaa"), Some("aaa"));
     assert_eq!(common_substring("", "bbaaa"), None);
+    assert_eq!(common_substring("abc", "xyz"), None);
     assert_eq!(common_substring("abcde", "abcde"), Some("abcde"));
     assert_eq!(common_substring("aaaaaaaaaaaaaaaaaaaaaaaaab", "aaaaaaaaaaaaaaaaaaaaaaaaac"), Some("aaaaaaaaaaaaaaaaaaaaaaaaa"));
-}
-
-#[derive(Clone)]
-struct VertexAhoCorasick {
-    children: BTreeMap<i32, i32>,
-    link: i32,
-    good_link: i32,
-    pat_num: i32,
-    pch: i32,
-    parent: i32,
-}
-
-struct TrieAhoCorasick {
-    arr: Vec<VertexAhoCorasick>,
-    sz: i32,
-}
-
-impl TrieAhoCorasick {
-    fn new() -> TrieAhoCorasick {
-        TrieAhoCorasick { arr: vec![VertexAhoCorasick { children: BTreeMap::new(), link: -1, good_link: -1, pat_num: -1, pch: -1, parent: -1 }; 1], sz: 1 }
-    }
-    fn insert(&mut self, s: &str, num: i32) {
-        let mut v = 0;
-        for ch in s.as_bytes() {
-            let idx = *ch as i32;
-            if self.arr[v].children.get(&idx).is_none() {
-                self.arr.push(VertexAhoCorasick { children: BTreeMap::new(), link: 0, good_link: -1, pat_num: -1, pch: idx, parent: v as i32 });
-                self.arr[v].children.insert(idx, self.sz);
-                self.sz += 1;
-            }
-            v = *self.arr[v].children.get(&idx).unwrap() as usize;
-        }
-        self.arr[v].pat_num = num;
-    }
+    // 'é' and 'è' share the first byte
+    assert_eq!(common_substring("é", "è"), None);
+    assert_eq!(common_substring("привет мир", "мирный"), Some("мир"));
+    assert_eq!(common_substring("東京都", "京都府"), Some("京都"));
+
+    // with zero bases the hash depends only on the last byte, so almost all the windows collide
+    let (a, b) = ("qbab", "ab");
+    let ha = RollingHash::build_with_bases(a, (0, 0));
+    let hb = RollingHash::build_with_bases(b, (0, 0));
+    assert_eq!(ha.substring_hash(0, 2), hb.substring_hash(0, 2));
+    assert_eq!(common_substring_hashed(a, b, &ha, &hb), Some("ab"));
 }
 
 /// Algorithm Aho Corasick. Search for a set of substring from the dictionary in the given string.
+/// Builds the automaton on every call, use `AhoCorasick` to search many texts with the same dictionary.
+/// A pattern repeated in the dictionary is reported only under its last index.
 ///```
 /// use librualg::string::aho_corasick;
 /// use std::collections::BTreeMap;
@@ -613,62 +696,14 @@ impl TrieAhoCorasick {
 /// ```
 
 pub fn aho_corasick(dict: &[&str], t: &str) -> BTreeMap<i32, Vec<usize>> {
-    let mut res: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
-    let mut trie = TrieAhoCorasick::new();
-    for (idx, s) in dict.iter().enumerate() {
-        trie.insert(*s, idx as i32);
+    // a repeated pattern is reported only under its last index
+    let mut last: BTreeMap<&str, usize> = BTreeMap::new();
+    for (idx, pattern) in dict.iter().enumerate() {
+        last.insert(pattern, idx);
     }
-    let mut q = VecDeque::new();
-    q.push_back(0);
-    while !q.is_empty() {
-        let curr = q.pop_front().unwrap();
-
-        for value in trie.arr[curr as usize].children.values() {
-            q.push_back(*value);
-        }
-        if curr == 0 {
-            continue
-        }
-        let parent = trie.arr[curr as usize].parent;
-        let mut next_link = trie.arr[parent as usize].link;
-        let pch = trie.arr[curr as usize].pch;
-        while next_link >= 0 && trie.arr[next_link as usize].children.get(&pch).is_none() {
-            next_link = trie.arr[next_link as usize].link;
-        }
-        if next_link >= 0 {
-            let link = *trie.arr[next_link as usize].children.get(&pch).unwrap();
-            let good_link;
-            if trie.arr[link as usize].pat_num != -1 {
-                good_link = link;
-            } else {
-                good_link = trie.arr[link as usize].good_link;
-            }
-            let r = &mut trie.arr[curr as usize];
-            r.link = link;
-            r.good_link = good_link;
-        }
-    }
-    let mut v = 0i32;
-    for (i, ch) in t.as_bytes().iter().enumerate() {
-        let idx = *ch as i32;
-        while v >= 0 && trie.arr[v as usize].children.get(&idx).is_none() {
-            v = trie.arr[v as usize].link;
-        }
-        if v == -1 {
-            v = 0;
-        } else {
-            v = *trie.arr[v as usize].children.get(&idx).unwrap();
-        }
-        if trie.arr[v as usize].pat_num != -1 {
-            let value = res.entry(trie.arr[v as usize].pat_num).or_insert(vec![]);
-            (*value).push(i + 1 - dict[trie.arr[v as usize].pat_num as usize].len());
-        }
-        let mut good_link = trie.arr[v as usize].good_link;
-        while good_link > 0 {
-            let value = res.entry(trie.arr[good_link as usize].pat_num).or_insert(vec![]);
-            (*value).push(i + 1 - dict[trie.arr[good_link as usize].pat_num as usize].len());
-            good_link = trie.arr[good_link as usize].good_link;
-        }
+    let mut res: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
+    for found in AhoCorasick::new(dict).find_all(t).into_iter().filter(|found| last[dict[found.pattern]] == found.pattern) {
+        res.entry(found.pattern as i32).or_insert_with(Vec::new).push(found.start);
     }
     res
 }
@@ -721,4 +756,10 @@ fn test_aho_corasick() {
     m.insert(1, vec![1, 3, 5]);
     assert_eq!(m, res);
 
+    // a repeated pattern keeps only the last index
+    let res = aho_corasick(&["ab", "b", "ab"], "abab");
+    m = BTreeMap::new();
+    m.insert(1, vec![1, 3]);
+    m.insert(2, vec![0, 2]);
+    assert_eq!(m, res);
 }
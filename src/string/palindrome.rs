@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+
+/// Manacher's algorithm.
+/// `d1[i]` is the number of odd palindromes centered at `i` (the radius including the center),
+/// `d2[i]` is the number of even palindromes centered between `i - 1` and `i`.
+///```
+/// use librualg::string::manacher;
+///
+/// let (d1, d2) = manacher("abaab");
+/// assert_eq!(d1, vec![1, 2, 1, 1, 1]);
+/// assert_eq!(d2, vec![0, 0, 0, 2, 0]);
+/// ```
+pub fn manacher(src: &str) -> (Vec<usize>, Vec<usize>) {
+    manacher_slice(src.as_bytes())
+}
+
+/// Manacher's algorithm over slices
+///```
+/// use librualg::string::manacher_slice;
+///
+/// let text: Vec<char> = "шалаш".chars().collect();
+/// assert_eq!(manacher_slice(&text).0, vec![1, 1, 3, 1, 1]);
+/// ```
+pub fn manacher_slice<T: Eq>(s: &[T]) -> (Vec<usize>, Vec<usize>) {
+    let n = s.len();
+    let mut d1 = vec![0; n];
+    let (mut l, mut r) = (0, 0);
+    for i in 0..n {
+        let mut k = if i < r { d1[l + r - 1 - i].min(r - i) } else { 1 };
+        while i + k < n && i >= k && s[i + k] == s[i - k] {
+            k += 1;
+        }
+        d1[i] = k;
+        if i + k > r {
+            l = i + 1 - k;
+            r = i + k;
+        }
+    }
+    let mut d2 = vec![0; n];
+    let (mut l, mut r) = (0, 0);
+    for i in 0..n {
+        let mut k = if i < r { d2[l + r - i].min(r - i) } else { 0 };
+        while i + k < n && i > k && s[i + k] == s[i - k - 1] {
+            k += 1;
+        }
+        d2[i] = k;
+        if i + k > r {
+            l = i - k;
+            r = i + k;
+        }
+    }
+    (d1, d2)
+}
+
+/// Longest palindromic substring of `char`s (the leftmost one if there are several)
+///```
+/// use librualg::string::longest_palindrome;
+///
+/// assert_eq!(longest_palindrome("forgeeksskeegfor"), "geeksskeeg");
+/// assert_eq!(longest_palindrome("abacdfgdcaba"), "aba");
+/// assert_eq!(longest_palindrome(""), "");
+/// assert_eq!(longest_palindrome("на шалаше"), "шалаш");
+/// ```
+pub fn longest_palindrome(src: &str) -> &str {
+    let chars: Vec<char> = src.chars().collect();
+    let offsets: Vec<usize> = src.char_indices().map(|(offset, _)| offset).chain(Some(src.len())).collect();
+    let (d1, d2) = manacher_slice(&chars);
+    let mut best = (0, 0);
+    for i in 0..chars.len() {
+        let (start, len) = (i + 1 - d1[i], 2 * d1[i] - 1);
+        if len > best.1 || (len == best.1 && start < best.0) {
+            best = (start, len);
+        }
+        let (start, len) = (i - d2[i], 2 * d2[i]);
+        if len > best.1 || (len == best.1 && start < best.0) {
+            best = (start, len);
+        }
+    }
+    &src[offsets[best.0]..offsets[best.0 + best.1]]
+}
+
+struct Node {
+    len: isize,
+    link: usize,
+    next: BTreeMap<char, usize>,
+    // number of palindromic suffixes of this palindrome (including itself)
+    depth: usize,
+    // end position (in chars) of the first occurrence
+    end: usize,
+}
+
+/// Palindromic tree (eertree): one node per distinct palindromic substring.
+/// The tree is built over `char`s, the positions are the indices of `char`s.
+///```
+/// use librualg::string::PalindromicTree;
+///
+/// let tree = PalindromicTree::build("abaaba");
+/// assert_eq!(tree.count_distinct(), 6);
+/// assert_eq!(tree.palindromes(), vec!["a", "aa", "aba", "abaaba", "b", "baab"]);
+/// assert_eq!(tree.prefix_distinct_counts(), vec![1, 2, 3, 4, 5, 6]);
+/// assert_eq!(tree.ending_at(), vec![1, 1, 2, 2, 2, 3]);
+/// assert_eq!(tree.count_all(), 11);
+/// ```
+pub struct PalindromicTree<'a> {
+    text: &'a str,
+    // byte offsets of the chars and the length of the text
+    offsets: Vec<usize>,
+    nodes: Vec<Node>,
+    ending_at: Vec<usize>,
+    distinct: Vec<usize>,
+}
+
+impl<'a> PalindromicTree<'a> {
+    pub fn build(text: &'a str) -> Self {
+        let s: Vec<char> = text.chars().collect();
+        let offsets: Vec<usize> = text.char_indices().map(|(offset, _)| offset).chain(Some(text.len())).collect();
+        // 0: the imaginary root of length -1, 1: the empty palindrome
+        let mut nodes = vec![
+            Node { len: -1, link: 0, next: BTreeMap::new(), depth: 0, end: 0 },
+            Node { len: 0, link: 0, next: BTreeMap::new(), depth: 0, end: 0 },
+        ];
+        let mut ending_at = Vec::with_capacity(s.len());
+        let mut distinct = Vec::with_capacity(s.len());
+        let suffix_of = |nodes: &[Node], mut v: usize, i: usize| {
+            loop {
+                let len = nodes[v].len;
+                if i as isize - len > 0 && s[(i as isize - len - 1) as usize] == s[i] {
+                    return v;
+                }
+                v = nodes[v].link;
+            }
+        };
+        let mut last = 1;
+        for (i, ch) in s.iter().enumerate() {
+            let v = suffix_of(&nodes, last, i);
+            last = match nodes[v].next.get(ch) {
+                Some(to) => *to,
+                None => {
+                    let len = nodes[v].len + 2;
+                    let link = if len == 1 {
+                        1
+                    } else {
+                        let u = suffix_of(&nodes, nodes[v].link, i);
+                        nodes[u].next[ch]
+                    };
+                    let depth = nodes[link].depth + 1;
+                    nodes.push(Node { len, link, next: BTreeMap::new(), depth, end: i });
+                    let curr = nodes.len() - 1;
+                    nodes[v].next.insert(*ch, curr);
+                    curr
+                }
+            };
+            ending_at.push(nodes[last].depth);
+            distinct.push(nodes.len() - 2);
+        }
+        PalindromicTree { text, offsets, nodes, ending_at, distinct }
+    }
+
+    /// Number of distinct non-empty palindromic substrings
+    pub fn count_distinct(&self) -> usize {
+        self.nodes.len() - 2
+    }
+
+    /// Number of palindromic substrings counted with multiplicity
+    pub fn count_all(&self) -> usize {
+        self.ending_at.iter().sum()
+    }
+
+    /// Distinct palindromic substrings in lexicographic order
+    pub fn palindromes(&self) -> Vec<&'a str> {
+        let mut res: Vec<&'a str> = self.nodes.iter().skip(2).map(|node| {
+            let len = node.len as usize;
+            &self.text[self.offsets[node.end + 1 - len]..self.offsets[node.end + 1]]
+        }).collect();
+        res.sort_unstable();
+        res
+    }
+
+    /// `res[i]` is the number of distinct palindromic substrings of the prefix of `i + 1` chars
+    pub fn prefix_distinct_counts(&self) -> Vec<usize> {
+        self.distinct.clone()
+    }
+
+    /// `res[i]` is the number of palindromic substrings ending at the `i`-th char
+    pub fn ending_at(&self) -> Vec<usize> {
+        self.ending_at.clone()
+    }
+}
+
+#[test]
+fn test_manacher() {
+    for text in &["", "a", "aa", "abc", "abaab", "aaaaa", "abacabadabacaba", "forgeeksskeegfor"] {
+        let s = text.as_bytes();
+        let is_palindrome = |l: usize, r: usize| (l..r).all(|i| s[i] == s[r - 1 - (i - l)]);
+        let (d1, d2) = manacher(text);
+        for i in 0..s.len() {
+            let mut k = 1;
+            while i >= k && i + k < s.len() && is_palindrome(i - k, i + k + 1) {
+                k += 1;
+            }
+            assert_eq!(d1[i], k);
+            let mut k = 0;
+            while i > k && i + k < s.len() && is_palindrome(i - k - 1, i + k + 1) {
+                k += 1;
+            }
+            assert_eq!(d2[i], k);
+        }
+    }
+    assert_eq!(longest_palindrome("abcd"), "a");
+    assert_eq!(longest_palindrome("abba"), "abba");
+    assert_eq!(longest_palindrome("cabbad"), "abba");
+}
+
+#[test]
+fn test_palindromic_tree() {
+    use std::collections::BTreeSet;
+
+    for text in &["", "a", "aaaa", "abacaba", "eertree", "mississippi", "abaabaab"] {
+        let tree = PalindromicTree::build(text);
+        let s = text.as_bytes();
+        let is_palindrome = |l: usize, r: usize| (l..r).all(|i| s[i] == s[r - 1 - (i - l)]);
+        let mut set = BTreeSet::new();
+        let mut all = 0;
+        for r in 1..=s.len() {
+            let mut ending = 0;
+            for l in 0..r {
+                if is_palindrome(l, r) {
+                    set.insert(&text[l..r]);
+                    ending += 1;
+                }
+            }
+            assert_eq!(tree.ending_at()[r - 1], ending);
+            assert_eq!(tree.prefix_distinct_counts()[r - 1], set.len());
+            all += ending;
+        }
+        assert_eq!(tree.count_distinct(), set.len());
+        assert_eq!(tree.palindromes(), set.into_iter().collect::<Vec<&str>>());
+        assert_eq!(tree.count_all(), all);
+    }
+}
+
+#[test]
+fn test_palindromes_unicode() {
+    // the bytes of "ыы" are D1 8B D1 8B
+    assert_eq!(longest_palindrome("ыы"), "ыы");
+    assert_eq!(longest_palindrome("ыя"), "ы");
+    assert_eq!(longest_palindrome("上海自来水来自海上"), "上海自来水来自海上");
+    let tree = PalindromicTree::build("ыяы");
+    assert_eq!(tree.palindromes(), vec!["ы", "ыяы", "я"]);
+    assert_eq!(tree.ending_at(), vec![1, 1, 2]);
+    assert_eq!(tree.count_all(), 4);
+}
@@ -0,0 +1,336 @@
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use crate::sparse_table::SparseTableMin;
+use crate::string::prefix_function_slice;
+use crate::string::sais::sa_is;
+
+/// Lyndon factorization (Duval's algorithm): the string is split into the non-increasing sequence
+/// of Lyndon words (strings strictly smaller than all their proper rotations). Works over `char`s.
+///```
+/// use librualg::string::lyndon_factorization;
+///
+/// assert_eq!(lyndon_factorization("banana"), vec!["b", "an", "an", "a"]);
+/// assert_eq!(lyndon_factorization("abcab"), vec!["abc", "ab"]);
+/// ```
+pub fn lyndon_factorization(src: &str) -> Vec<&str> {
+    let (chars, offsets) = chars_with_offsets(src);
+    let starts = lyndon_factorization_slice(&chars);
+    starts.iter().enumerate().map(|(idx, &start)| {
+        let end = starts.get(idx + 1).cloned().unwrap_or(chars.len());
+        &src[offsets[start]..offsets[end]]
+    }).collect()
+}
+
+/// Lyndon factorization over slices, returns the starts of the factors
+///```
+/// use librualg::string::lyndon_factorization_slice;
+///
+/// assert_eq!(lyndon_factorization_slice(&[3, 1, 2, 1, 2, 1]), vec![0, 1, 3, 5]);
+/// ```
+pub fn lyndon_factorization_slice<T: Ord>(src: &[T]) -> Vec<usize> {
+    let n = src.len();
+    let mut res = vec![];
+    let mut i = 0;
+    while i < n {
+        let (mut j, mut k) = (i + 1, i);
+        while j < n && src[k] <= src[j] {
+            if src[k] < src[j] {
+                k = i;
+            } else {
+                k += 1;
+            }
+            j += 1;
+        }
+        while i <= k {
+            res.push(i);
+            i += j - k;
+        }
+    }
+    res
+}
+
+/// Lexicographically least rotation (the canonical form of a cyclic string), Booth's algorithm over `char`s
+///```
+/// use librualg::string::minimal_rotation;
+///
+/// assert_eq!(minimal_rotation("bbaab"), "aabbb");
+/// assert_eq!(minimal_rotation("cabca"), "abcac");
+/// ```
+pub fn minimal_rotation(src: &str) -> String {
+    let chars: Vec<char> = src.chars().collect();
+    let start = minimal_rotation_slice(&chars);
+    chars[start..].iter().chain(chars[..start].iter()).collect()
+}
+
+/// Start of the lexicographically least rotation (the leftmost one if there are several), Booth's algorithm
+///```
+/// use librualg::string::minimal_rotation_slice;
+///
+/// assert_eq!(minimal_rotation_slice(&[3, 1, 2, 1, 1]), 3);
+/// assert_eq!(minimal_rotation_slice(&[1, 2, 1, 2]), 0);
+/// ```
+pub fn minimal_rotation_slice<T: Ord>(src: &[T]) -> usize {
+    let n = src.len();
+    if n == 0 {
+        return 0;
+    }
+    // failure function of the doubled string starting at the current candidate `k`
+    let mut fail: Vec<Option<usize>> = vec![None; 2 * n];
+    let mut k = 0;
+    for j in 1..2 * n {
+        let ch = &src[j % n];
+        let mut i = fail[j - k - 1];
+        loop {
+            let next = &src[(k + i.map_or(0, |i| i + 1)) % n];
+            if ch == next {
+                fail[j - k] = Some(i.map_or(0, |i| i + 1));
+                break;
+            }
+            if ch < next {
+                k = j - i.map_or(0, |i| i + 1);
+            }
+            match i {
+                Some(idx) => i = fail[idx],
+                None => {
+                    fail[j - k] = None;
+                    break;
+                }
+            }
+        }
+    }
+    // for a power of a shorter string `k` can point to a later copy of the rotation
+    let period = crate::string::minimum_period_slice(src);
+    match n % period {
+        0 => k % period,
+        _ => k,
+    }
+}
+
+/// Lengths of all the borders (non-empty proper prefixes that are also suffixes) in ascending order.
+/// Works over bytes, the lengths are in bytes (`borders_slice` over a `Vec<char>` gives them in `char`s).
+///```
+/// use librualg::string::borders;
+///
+/// assert_eq!(borders("abacaba"), vec![1, 3]);
+/// assert_eq!(borders("abc"), vec![]);
+/// ```
+pub fn borders(src: &str) -> Vec<usize> {
+    borders_slice(src.as_bytes())
+}
+
+pub fn borders_slice<T: Eq>(src: &[T]) -> Vec<usize> {
+    let pi = prefix_function_slice(src);
+    let mut res = vec![];
+    let mut len = pi.last().cloned().unwrap_or(0);
+    while len > 0 {
+        res.push(len);
+        len = pi[len - 1];
+    }
+    res.reverse();
+    res
+}
+
+/// All periods `p` (`src[i] == src[i + p]`), the length of the string included, in ascending order.
+/// Works over bytes, the periods are in bytes.
+///```
+/// use librualg::string::periods;
+///
+/// assert_eq!(periods("abacaba"), vec![4, 6, 7]);
+/// assert_eq!(periods("aaa"), vec![1, 2, 3]);
+/// ```
+pub fn periods(src: &str) -> Vec<usize> {
+    periods_slice(src.as_bytes())
+}
+
+pub fn periods_slice<T: Eq>(src: &[T]) -> Vec<usize> {
+    let mut res: Vec<usize> = borders_slice(src).iter().rev().map(|border| src.len() - border).collect();
+    if !src.is_empty() {
+        res.push(src.len());
+    }
+    res
+}
+
+/// Maximal repetition: `src[start..end]` has the smallest period `period`, is at least two periods long
+/// and can not be extended keeping the period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Run {
+    pub start: usize,
+    pub end: usize,
+    pub period: usize,
+}
+
+/// All runs (maximal repetitions) of the string in O(n log n): every run has a Lyndon root that is
+/// the longest Lyndon word starting at its position for the usual or the inverted order of the symbols.
+/// Works over `char`s, the positions and the periods are in bytes.
+///```
+/// use librualg::string::{runs, Run};
+///
+/// assert_eq!(runs("aabaabaa"), vec![
+///     Run { start: 0, end: 2, period: 1 },
+///     Run { start: 0, end: 8, period: 3 },
+///     Run { start: 3, end: 5, period: 1 },
+///     Run { start: 6, end: 8, period: 1 },
+/// ]);
+/// ```
+pub fn runs(src: &str) -> Vec<Run> {
+    let (chars, offsets) = chars_with_offsets(src);
+    runs_slice(&chars).into_iter().map(|run| Run {
+        start: offsets[run.start],
+        end: offsets[run.end],
+        period: offsets[run.start + run.period] - offsets[run.start],
+    }).collect()
+}
+
+/// All runs of the slice, sorted by the start and the end
+pub fn runs_slice<T: Ord>(src: &[T]) -> Vec<Run> {
+    let n = src.len();
+    let forward = Lce::build(src.iter().collect());
+    let backward = Lce::build(src.iter().rev().collect());
+    let inverted = Lce::build(src.iter().map(Reverse).collect());
+    let mut res = BTreeSet::new();
+    for rank in &[&forward.rank, &inverted.rank] {
+        // next smaller suffix: the longest Lyndon word starting at `i` ends there
+        let mut stack: Vec<usize> = vec![];
+        for i in (0..n).rev() {
+            while let Some(&top) = stack.last() {
+                if rank[top] > rank[i] {
+                    stack.pop();
+                } else {
+                    break;
+                }
+            }
+            let j = stack.last().cloned().unwrap_or(n);
+            stack.push(i);
+            let period = j - i;
+            let right = if j < n { forward.lcp(i, j) } else { 0 };
+            let left = if i > 0 { backward.lcp(n - i, n - j) } else { 0 };
+            if left + right >= period {
+                res.insert(Run { start: i - left, end: j + right, period });
+            }
+        }
+    }
+    res.into_iter().collect()
+}
+
+fn chars_with_offsets(src: &str) -> (Vec<char>, Vec<usize>) {
+    let mut offsets: Vec<usize> = src.char_indices().map(|(idx, _)| idx).collect();
+    offsets.push(src.len());
+    (src.chars().collect(), offsets)
+}
+
+// longest common extensions of the suffixes
+struct Lce {
+    rank: Vec<usize>,
+    table: SparseTableMin<usize>,
+}
+
+impl Lce {
+    fn build<T: Ord>(src: Vec<T>) -> Self {
+        // the symbols are renamed to `1..` in their order, `0` is the unique smallest last symbol
+        let mut order: Vec<usize> = (0..src.len()).collect();
+        order.sort_by(|&a, &b| src[a].cmp(&src[b]));
+        let mut codes = vec![0; src.len() + 1];
+        let mut upper = 0;
+        for (idx, &pos) in order.iter().enumerate() {
+            if idx == 0 || src[order[idx - 1]] != src[pos] {
+                upper += 1;
+            }
+            codes[pos] = upper;
+        }
+        let sa = sa_is(&codes, upper);
+        let src = codes;
+        let n = src.len();
+        let mut rank = vec![0; n];
+        for (i, item) in sa.iter().enumerate() {
+            rank[*item] = i;
+        }
+        let mut lcp = vec![0; n];
+        let mut k: usize = 0;
+        for i in 0..n {
+            if rank[i] == 0 {
+                k = 0;
+                continue;
+            }
+            let j = sa[rank[i] - 1];
+            while i + k < n && j + k < n && src[i + k] == src[j + k] {
+                k += 1;
+            }
+            lcp[rank[i]] = k;
+            k = k.saturating_sub(1);
+        }
+        Lce { table: SparseTableMin::build(&lcp), rank }
+    }
+
+    fn lcp(&self, i: usize, j: usize) -> usize {
+        let (a, b) = if self.rank[i] < self.rank[j] { (self.rank[i], self.rank[j]) } else { (self.rank[j], self.rank[i]) };
+        self.table.query(a + 1, b)
+    }
+}
+
+#[cfg(test)]
+fn runs_naive(src: &[u8]) -> Vec<Run> {
+    let n = src.len();
+    let mut res = vec![];
+    for start in 0..n {
+        for end in start + 1..=n {
+            let period = crate::string::minimum_period_slice(&src[start..end]);
+            let left = start > 0 && src[start - 1] == src[start - 1 + period];
+            let right = end < n && src[end] == src[end - period];
+            if 2 * period <= end - start && !left && !right {
+                res.push(Run { start, end, period });
+            }
+        }
+    }
+    res.sort();
+    res
+}
+
+#[test]
+fn test_lyndon_and_rotation() {
+    use rand::{Rng, SeedableRng, StdRng};
+
+    let mut rng = StdRng::from_seed(&[5]);
+    for _ in 0..300 {
+        let src: Vec<u8> = (0..rng.gen_range(0, 14)).map(|_| rng.gen_range(0, 3)).collect();
+        let starts = lyndon_factorization_slice(&src);
+        let mut factors = vec![];
+        for (idx, &start) in starts.iter().enumerate() {
+            factors.push(&src[start..starts.get(idx + 1).cloned().unwrap_or(src.len())]);
+        }
+        for factor in &factors {
+            for shift in 1..factor.len() {
+                let rotated: Vec<u8> = factor[shift..].iter().chain(factor[..shift].iter()).cloned().collect();
+                assert!(factor.to_vec() < rotated);
+            }
+        }
+        assert!(factors.windows(2).all(|w| w[0] >= w[1]));
+
+        let expected = (0..src.len()).min_by_key(|&i| src[i..].iter().chain(src[..i].iter()).cloned().collect::<Vec<u8>>());
+        assert_eq!(minimal_rotation_slice(&src), expected.unwrap_or(0));
+    }
+    assert_eq!(lyndon_factorization(""), Vec::<&str>::new());
+    assert_eq!(lyndon_factorization("éa"), vec!["é", "a"]);
+    assert_eq!(minimal_rotation("яблоко"), "блокоя");
+}
+
+#[test]
+fn test_periods_and_borders() {
+    assert_eq!(borders(""), vec![]);
+    assert_eq!(periods(""), vec![]);
+    assert_eq!(borders("aaaa"), vec![1, 2, 3]);
+    assert_eq!(periods("abaababaab"), vec![5, 8, 10]);
+    assert_eq!(periods_slice(&[1, 2, 1, 2, 1]), vec![2, 4, 5]);
+}
+
+#[test]
+fn test_runs() {
+    use rand::{Rng, SeedableRng, StdRng};
+
+    let mut rng = StdRng::from_seed(&[6]);
+    for _ in 0..300 {
+        let src: Vec<u8> = (0..rng.gen_range(0, 20)).map(|_| rng.gen_range(0, 3)).collect();
+        assert_eq!(runs_slice(&src), runs_naive(&src));
+    }
+    assert_eq!(runs("ééx"), vec![Run { start: 0, end: 4, period: 2 }]);
+    assert_eq!(runs("abc"), vec![]);
+}
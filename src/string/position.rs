@@ -0,0 +1,120 @@
+use crate::string::{kmp_slice, z_function_slice};
+
+/// Position in a text: the byte offset and the index of the `char`
+///```
+/// use librualg::string::TextPosition;
+///
+/// let text = "日本語";
+/// assert_eq!(TextPosition::from_byte(text, 3), Some(TextPosition { byte: 3, char: 1 }));
+/// assert_eq!(TextPosition::from_byte(text, 4), None);
+/// assert_eq!(TextPosition::from_char(text, 3), Some(TextPosition { byte: 9, char: 3 }));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextPosition {
+    pub byte: usize,
+    pub char: usize,
+}
+
+impl TextPosition {
+    /// Position of the byte offset, None if the offset is inside a code point or after the end of the text
+    pub fn from_byte(text: &str, byte: usize) -> Option<Self> {
+        if !text.is_char_boundary(byte) {
+            return None;
+        }
+        Some(TextPosition { byte, char: text[..byte].chars().count() })
+    }
+
+    /// Position of the `char`-th char (the end of the text for the number of chars), None if it is after the end
+    pub fn from_char(text: &str, char: usize) -> Option<Self> {
+        let byte = text.char_indices().map(|(offset, _)| offset).chain(Some(text.len())).nth(char)?;
+        Some(TextPosition { byte, char })
+    }
+}
+
+/// Positions of the byte offsets (e.g. found by `kmp`, `aho_corasick` or `Regex`) in any order,
+/// None if one of the offsets is inside a code point or after the end of the text
+///```
+/// use librualg::string::{kmp, text_positions, TextPosition};
+///
+/// let text = "ёлка, ёж";
+/// let found = text_positions(text, &kmp(text, "ё")).unwrap();
+/// assert_eq!(found, vec![TextPosition { byte: 0, char: 0 }, TextPosition { byte: 10, char: 6 }]);
+/// assert_eq!(text_positions(text, &[1]), None);
+/// ```
+pub fn text_positions(text: &str, byte_offsets: &[usize]) -> Option<Vec<TextPosition>> {
+    let boundaries: Vec<usize> = text.char_indices().map(|(offset, _)| offset).chain(Some(text.len())).collect();
+    byte_offsets.iter().map(|&byte| {
+        boundaries.binary_search(&byte).ok().map(|char| TextPosition { byte, char })
+    }).collect()
+}
+
+/// Substring of the chars `start..end`, None if the range is out of the text
+///```
+/// use librualg::string::substring_chars;
+///
+/// assert_eq!(substring_chars("Привет, мир", 8, 11), Some("мир"));
+/// assert_eq!(substring_chars("Привет", 4, 7), None);
+/// ```
+pub fn substring_chars(text: &str, start: usize, end: usize) -> Option<&str> {
+    if start > end {
+        return None;
+    }
+    let from = TextPosition::from_char(text, start)?;
+    let to = TextPosition::from_char(&text[from.byte..], end - start)?;
+    Some(&text[from.byte..from.byte + to.byte])
+}
+
+/// Knuth–Morris–Pratt algorithm. Return all occurrences of a substring with the byte and the char offsets.
+///```
+/// use librualg::string::{kmp_chars, TextPosition};
+///
+/// assert_eq!(kmp_chars("東京と京都", "京"), vec![TextPosition { byte: 3, char: 1 }, TextPosition { byte: 9, char: 3 }]);
+/// ```
+pub fn kmp_chars(t: &str, p: &str) -> Vec<TextPosition> {
+    let text: Vec<char> = t.chars().collect();
+    let pattern: Vec<char> = p.chars().collect();
+    let boundaries: Vec<usize> = t.char_indices().map(|(offset, _)| offset).chain(Some(t.len())).collect();
+    kmp_slice(&text, &pattern).into_iter().map(|char| TextPosition { byte: boundaries[char], char }).collect()
+}
+
+/// Z-function over the chars of the string: `res[i]` is the number of the chars in the longest
+/// common prefix of the string and its suffix from the `i`-th char
+///```
+/// use librualg::string::z_function_chars;
+///
+/// assert_eq!(z_function_chars("абаба"), vec![0, 0, 3, 0, 1]);
+/// ```
+pub fn z_function_chars(src: &str) -> Vec<usize> {
+    let chars: Vec<char> = src.chars().collect();
+    z_function_slice(&chars)
+}
+
+#[test]
+fn test_text_position() {
+    let text = "añb日";
+    assert_eq!(TextPosition::from_byte(text, 0), Some(TextPosition { byte: 0, char: 0 }));
+    assert_eq!(TextPosition::from_byte(text, 2), None);
+    assert_eq!(TextPosition::from_byte(text, 4), Some(TextPosition { byte: 4, char: 3 }));
+    assert_eq!(TextPosition::from_byte(text, 7), Some(TextPosition { byte: 7, char: 4 }));
+    assert_eq!(TextPosition::from_byte(text, 8), None);
+    assert_eq!(TextPosition::from_char(text, 4), Some(TextPosition { byte: 7, char: 4 }));
+    assert_eq!(TextPosition::from_char(text, 5), None);
+    assert_eq!(text_positions(text, &[7, 0, 3]), Some(vec![
+        TextPosition { byte: 7, char: 4 }, TextPosition { byte: 0, char: 0 }, TextPosition { byte: 3, char: 2 }
+    ]));
+    assert_eq!(text_positions("", &[0]), Some(vec![TextPosition { byte: 0, char: 0 }]));
+}
+
+#[test]
+fn test_chars_variants() {
+    assert_eq!(substring_chars("añb日", 1, 4), Some("ñb日"));
+    assert_eq!(substring_chars("añb日", 2, 2), Some(""));
+    assert_eq!(substring_chars("añb日", 3, 2), None);
+    assert_eq!(substring_chars("", 0, 0), Some(""));
+    assert_eq!(kmp_chars("ааа", "аа"), vec![TextPosition { byte: 0, char: 0 }, TextPosition { byte: 2, char: 1 }]);
+    assert_eq!(kmp_chars("abc", "").len(), 4);
+    assert_eq!(kmp_chars("é", "è"), vec![]);
+    // the bytes of 'é' and 'è' share the first byte, the chars do not
+    assert_eq!(z_function_chars("éè"), vec![0, 0]);
+    assert_eq!(z_function_chars(""), Vec::<usize>::new());
+}
@@ -0,0 +1,897 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Mutex;
+
+// limit of the compiled program, the counted repetitions are expanded
+const MAX_PROGRAM: usize = 100_000;
+// limit of the counters in `{n,m}`
+const MAX_REPEAT: u32 = 1000;
+// limit of the nesting of the groups and the repetitions, the parser and the compiler recurse on them
+const MAX_NESTING: usize = 250;
+// the lazy DFA drops its states when there are more of them
+const MAX_DFA_STATES: usize = 4096;
+
+/// Error of the regular expression syntax, the positions are in characters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegexError {
+    /// The pattern ends inside a group, a class or an escape
+    UnexpectedEnd,
+    /// `)` without `(`
+    UnmatchedParen(usize),
+    /// `*`, `+`, `?` or `{` without an expression before it
+    NothingToRepeat(usize),
+    /// Malformed `{n,m}`
+    InvalidRepeat(usize),
+    /// Malformed `[...]`
+    InvalidClass(usize),
+    /// Unknown escape sequence
+    InvalidEscape(usize),
+    /// The compiled expression is too large or a counter of `{n,m}` is above 1000
+    TooBig,
+    /// Groups or repetitions are nested too deep
+    TooDeep(usize),
+}
+
+impl fmt::Display for RegexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegexError::UnexpectedEnd => write!(f, "unexpected end of the pattern"),
+            RegexError::UnmatchedParen(pos) => write!(f, "unmatched parenthesis at {}", pos),
+            RegexError::NothingToRepeat(pos) => write!(f, "nothing to repeat at {}", pos),
+            RegexError::InvalidRepeat(pos) => write!(f, "invalid repetition at {}", pos),
+            RegexError::InvalidClass(pos) => write!(f, "invalid character class at {}", pos),
+            RegexError::InvalidEscape(pos) => write!(f, "invalid escape at {}", pos),
+            RegexError::TooBig => write!(f, "the expression is too big"),
+            RegexError::TooDeep(pos) => write!(f, "too deep nesting at {}", pos),
+        }
+    }
+}
+
+impl std::error::Error for RegexError {}
+
+#[derive(Clone, Debug)]
+struct Class {
+    negated: bool,
+    ranges: Vec<(char, char)>,
+}
+
+impl Class {
+    fn contains(&self, ch: char) -> bool {
+        self.ranges.iter().any(|&(from, to)| from <= ch && ch <= to) != self.negated
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Empty,
+    Char(char),
+    // `.`, any character except `\n`
+    Any,
+    Class(Class),
+    Start,
+    End,
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat { node: Box<Node>, min: u32, max: Option<u32>, greedy: bool },
+    Group { node: Box<Node>, index: Option<usize> },
+}
+
+impl Node {
+    // compiles to no instructions and matches only the empty string
+    fn is_empty(&self) -> bool {
+        match self {
+            Node::Empty => true,
+            Node::Concat(nodes) | Node::Alternation(nodes) => nodes.iter().all(Node::is_empty),
+            Node::Repeat { node, .. } | Node::Group { node, index: None } => node.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    groups: usize,
+    // number of the open groups
+    depth: usize,
+    // nesting of the groups and the repetitions in the last parsed node
+    height: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn next(&mut self) -> Result<char, RegexError> {
+        let ch = self.peek().ok_or(RegexError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(ch)
+    }
+
+    fn parse(&mut self) -> Result<Node, RegexError> {
+        let node = self.alternation()?;
+        match self.peek() {
+            Some(_) => Err(RegexError::UnmatchedParen(self.pos)),
+            None => Ok(node),
+        }
+    }
+
+    fn alternation(&mut self) -> Result<Node, RegexError> {
+        let mut branches = vec![self.concat()?];
+        let mut height = self.height;
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            branches.push(self.concat()?);
+            height = height.max(self.height);
+        }
+        self.height = height;
+        Ok(if branches.len() == 1 { branches.pop().unwrap() } else { Node::Alternation(branches) })
+    }
+
+    fn concat(&mut self) -> Result<Node, RegexError> {
+        let mut nodes = vec![];
+        let mut height = 0;
+        while let Some(ch) = self.peek() {
+            if ch == '|' || ch == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.repeat(atom)?);
+            height = height.max(self.height);
+        }
+        self.height = height;
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.pop().unwrap(),
+            _ => Node::Concat(nodes),
+        })
+    }
+
+    fn atom(&mut self) -> Result<Node, RegexError> {
+        let start = self.pos;
+        self.height = 0;
+        Ok(match self.next()? {
+            '(' => {
+                let index = if self.chars[self.pos..].starts_with(&['?', ':']) {
+                    self.pos += 2;
+                    None
+                } else {
+                    self.groups += 1;
+                    Some(self.groups)
+                };
+                self.depth += 1;
+                if self.depth > MAX_NESTING {
+                    return Err(RegexError::TooDeep(start));
+                }
+                let node = self.alternation()?;
+                self.depth -= 1;
+                self.height += 1;
+                if self.height > MAX_NESTING {
+                    return Err(RegexError::TooDeep(start));
+                }
+                if self.next()? != ')' {
+                    return Err(RegexError::UnmatchedParen(start));
+                }
+                Node::Group { node: Box::new(node), index }
+            }
+            '[' => Node::Class(self.class(start)?),
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '\\' => self.escape(start)?,
+            '*' | '+' | '?' | '{' => return Err(RegexError::NothingToRepeat(start)),
+            ch => Node::Char(ch),
+        })
+    }
+
+    fn escape(&mut self, start: usize) -> Result<Node, RegexError> {
+        let ch = self.next()?;
+        if let Some(class) = shorthand(ch) {
+            return Ok(Node::Class(class));
+        }
+        escaped_char(ch).map(Node::Char).ok_or(RegexError::InvalidEscape(start))
+    }
+
+    fn class(&mut self, start: usize) -> Result<Class, RegexError> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut ranges = vec![];
+        let mut first = true;
+        loop {
+            let pos = self.pos;
+            let mut ch = self.next()?;
+            if ch == ']' && !first {
+                break;
+            }
+            first = false;
+            if ch == '\\' {
+                let escaped = self.next()?;
+                if let Some(class) = shorthand(escaped) {
+                    ranges.extend(if class.negated { complement(&class.ranges) } else { class.ranges });
+                    continue;
+                }
+                ch = escaped_char(escaped).ok_or(RegexError::InvalidEscape(pos))?;
+            }
+            if self.peek() == Some('-') && matches!(self.chars.get(self.pos + 1), Some(&next) if next != ']') {
+                self.pos += 1;
+                let mut to = self.next()?;
+                if to == '\\' {
+                    to = escaped_char(self.next()?).ok_or(RegexError::InvalidEscape(self.pos - 2))?;
+                }
+                if to < ch {
+                    return Err(RegexError::InvalidClass(start));
+                }
+                ranges.push((ch, to));
+            } else {
+                ranges.push((ch, ch));
+            }
+        }
+        Ok(Class { negated, ranges })
+    }
+
+    fn repeat(&mut self, mut node: Node) -> Result<Node, RegexError> {
+        loop {
+            let start = self.pos;
+            let (min, max) = match self.peek() {
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('?') => (0, Some(1)),
+                Some('{') => {
+                    self.pos += 1;
+                    let min = self.number().ok_or(RegexError::InvalidRepeat(start))?;
+                    let max = match self.next()? {
+                        '}' => {
+                            self.pos -= 1;
+                            Some(min)
+                        }
+                        ',' if self.peek() == Some('}') => None,
+                        ',' => Some(self.number().ok_or(RegexError::InvalidRepeat(start))?),
+                        _ => return Err(RegexError::InvalidRepeat(start)),
+                    };
+                    if self.peek() != Some('}') || matches!(max, Some(max) if max < min) {
+                        return Err(RegexError::InvalidRepeat(start));
+                    }
+                    if min.max(max.unwrap_or(0)) > MAX_REPEAT {
+                        return Err(RegexError::TooBig);
+                    }
+                    (min, max)
+                }
+                _ => return Ok(node),
+            };
+            self.height += 1;
+            if self.height > MAX_NESTING {
+                return Err(RegexError::TooDeep(start));
+            }
+            self.pos += 1;
+            let greedy = self.peek() != Some('?');
+            if !greedy {
+                self.pos += 1;
+            }
+            node = Node::Repeat { node: Box::new(node), min, max, greedy };
+        }
+    }
+
+    fn number(&mut self) -> Option<u32> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(ch) if ch.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect::<String>().parse().ok()
+    }
+}
+
+fn shorthand(ch: char) -> Option<Class> {
+    let ranges = match ch.to_ascii_lowercase() {
+        'd' => vec![('0', '9')],
+        'w' => vec![('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')],
+        's' => vec![('\t', '\r'), (' ', ' ')],
+        _ => return None,
+    };
+    Some(Class { negated: ch.is_ascii_uppercase(), ranges })
+}
+
+fn escaped_char(ch: char) -> Option<char> {
+    match ch {
+        'n' => Some('\n'),
+        't' => Some('\t'),
+        'r' => Some('\r'),
+        '0' => Some('\0'),
+        ch if ch.is_ascii_punctuation() || ch == ' ' => Some(ch),
+        _ => None,
+    }
+}
+
+// the characters outside of the ranges
+fn complement(ranges: &[(char, char)]) -> Vec<(char, char)> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_unstable();
+    let mut res = vec![];
+    let mut next = 0u32;
+    for (from, to) in sorted {
+        if (from as u32) > next {
+            push_range(&mut res, next, from as u32 - 1);
+        }
+        next = next.max(to as u32 + 1);
+    }
+    push_range(&mut res, next, char::MAX as u32);
+    res
+}
+
+fn push_range(res: &mut Vec<(char, char)>, from: u32, to: u32) {
+    // skips the surrogates
+    let from = if (0xD800..0xE000).contains(&from) { 0xE000 } else { from };
+    let to = if (0xD800..0xE000).contains(&to) { 0xD7FF } else { to };
+    if let (Some(from), Some(to)) = (std::char::from_u32(from), std::char::from_u32(to)) {
+        if from <= to {
+            res.push((from, to));
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(usize),
+    // both branches, the first one is preferred
+    Split(usize, usize),
+    Jump(usize),
+    Save(usize),
+    AssertStart,
+    AssertEnd,
+    Match,
+}
+
+struct Compiler {
+    program: Vec<Inst>,
+    classes: Vec<Class>,
+}
+
+impl Compiler {
+    fn emit(&mut self, inst: Inst) -> Result<usize, RegexError> {
+        if self.program.len() >= MAX_PROGRAM {
+            return Err(RegexError::TooBig);
+        }
+        self.program.push(inst);
+        Ok(self.program.len() - 1)
+    }
+
+    fn compile(&mut self, node: &Node) -> Result<(), RegexError> {
+        match node {
+            Node::Empty => {}
+            Node::Char(ch) => {
+                self.emit(Inst::Char(*ch))?;
+            }
+            Node::Any => {
+                self.emit(Inst::Any)?;
+            }
+            Node::Class(class) => {
+                self.classes.push(class.clone());
+                self.emit(Inst::Class(self.classes.len() - 1))?;
+            }
+            Node::Start => {
+                self.emit(Inst::AssertStart)?;
+            }
+            Node::End => {
+                self.emit(Inst::AssertEnd)?;
+            }
+            Node::Concat(nodes) => {
+                for node in nodes {
+                    self.compile(node)?;
+                }
+            }
+            Node::Alternation(branches) => {
+                let mut jumps = vec![];
+                for (idx, branch) in branches.iter().enumerate() {
+                    if idx + 1 < branches.len() {
+                        let split = self.emit(Inst::Split(0, 0))?;
+                        self.compile(branch)?;
+                        jumps.push(self.emit(Inst::Jump(0))?);
+                        self.program[split] = Inst::Split(split + 1, self.program.len());
+                    } else {
+                        self.compile(branch)?;
+                    }
+                }
+                let end = self.program.len();
+                for jump in jumps {
+                    self.program[jump] = Inst::Jump(end);
+                }
+            }
+            Node::Group { node, index } => match index {
+                Some(index) => {
+                    self.emit(Inst::Save(2 * index))?;
+                    self.compile(node)?;
+                    self.emit(Inst::Save(2 * index + 1))?;
+                }
+                None => self.compile(node)?,
+            },
+            // the repetitions of an empty node would only spend the time
+            Node::Repeat { node, .. } if node.is_empty() => {}
+            Node::Repeat { node, min, max, greedy } => {
+                for _ in 0..*min {
+                    self.compile(node)?;
+                }
+                match max {
+                    None => {
+                        let split = self.emit(Inst::Split(0, 0))?;
+                        self.compile(node)?;
+                        self.emit(Inst::Jump(split))?;
+                        self.program[split] = self.split(split + 1, self.program.len(), *greedy);
+                    }
+                    Some(max) => {
+                        let mut splits = vec![];
+                        for _ in *min..*max {
+                            splits.push(self.emit(Inst::Split(0, 0))?);
+                            self.compile(node)?;
+                        }
+                        let end = self.program.len();
+                        for split in splits {
+                            self.program[split] = self.split(split + 1, end, *greedy);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn split(&self, body: usize, exit: usize, greedy: bool) -> Inst {
+        if greedy {
+            Inst::Split(body, exit)
+        } else {
+            Inst::Split(exit, body)
+        }
+    }
+}
+
+// lazily built DFA over the sets of the NFA states, answers `is_match`
+#[derive(Default)]
+struct Dfa {
+    states: Vec<Vec<usize>>,
+    index: HashMap<Vec<usize>, usize>,
+    transitions: HashMap<(usize, char), usize>,
+    // number of the cache drops
+    generation: usize,
+}
+
+impl Dfa {
+    fn state(&mut self, pcs: Vec<usize>) -> usize {
+        if let Some(&id) = self.index.get(&pcs) {
+            return id;
+        }
+        if self.states.len() >= MAX_DFA_STATES {
+            self.states.clear();
+            self.index.clear();
+            self.transitions.clear();
+            self.generation += 1;
+        }
+        self.states.push(pcs.clone());
+        self.index.insert(pcs, self.states.len() - 1);
+        self.states.len() - 1
+    }
+}
+
+/// Captured groups of a match, the group 0 is the whole match
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Captures<'a> {
+    text: &'a str,
+    slots: Vec<Option<usize>>,
+}
+
+impl<'a> Captures<'a> {
+    /// Number of the groups, the whole match included
+    pub fn len(&self) -> usize {
+        self.slots.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Byte range of the group, None if the group did not participate in the match
+    pub fn range(&self, group: usize) -> Option<Range<usize>> {
+        match (self.slots.get(2 * group), self.slots.get(2 * group + 1)) {
+            (Some(Some(start)), Some(Some(end))) => Some(*start..*end),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, group: usize) -> Option<&'a str> {
+        self.range(group).map(|range| &self.text[range])
+    }
+}
+
+/// Regular expression with guaranteed linear time matching: `is_match`, `find` and `captures`
+/// take O(n·m) for the text of n bytes and the program of m instructions.
+/// The pattern is compiled into a Thompson NFA; `is_match` runs a lazily built DFA,
+/// `find` and `captures` run a Pike VM with the leftmost-first semantics (like Perl, the preferred branch wins).
+///
+/// Syntax: literals, `.` (any character except `\n`), `[a-z]`, `[^...]`, `\d \w \s \D \W \S`,
+/// `^`, `$`, `(...)`, `(?:...)`, `|`, `*`, `+`, `?`, `{n}`, `{n,}`, `{n,m}` and the lazy `*?`, `+?`, `??`, `{n,m}?`.
+/// The counters of `{n,m}` are at most 1000. The positions of the matches are in bytes.
+///```
+/// use librualg::string::Regex;
+///
+/// let re = Regex::new(r"(\w+)\s*=\s*(\d+)").unwrap();
+/// assert!(re.is_match("timeout = 30"));
+/// let caps = re.captures("set timeout = 30;").unwrap();
+/// assert_eq!(caps.get(0), Some("timeout = 30"));
+/// assert_eq!(caps.get(1), Some("timeout"));
+/// assert_eq!(caps.get(2), Some("30"));
+///
+/// let re = Regex::new("a+?").unwrap();
+/// assert_eq!(re.find("baaa"), Some(1..2));
+/// assert_eq!(Regex::new("a{2,1}").err().unwrap().to_string(), "invalid repetition at 1");
+/// ```
+pub struct Regex {
+    program: Vec<Inst>,
+    classes: Vec<Class>,
+    slots: usize,
+    dfa: Mutex<Dfa>,
+}
+
+impl Clone for Regex {
+    fn clone(&self) -> Self {
+        Regex { program: self.program.clone(), classes: self.classes.clone(), slots: self.slots, dfa: Mutex::new(Dfa::default()) }
+    }
+}
+
+impl fmt::Debug for Regex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Regex").field("program", &self.program).finish()
+    }
+}
+
+enum Frame {
+    Pc(usize),
+    Restore(usize, Option<usize>),
+}
+
+// threads of the Pike VM in the order of their priority
+struct Threads {
+    visited: Vec<bool>,
+    marked: Vec<usize>,
+    list: Vec<(usize, Vec<Option<usize>>)>,
+}
+
+impl Threads {
+    fn new(len: usize) -> Self {
+        Threads { visited: vec![false; len], marked: vec![], list: vec![] }
+    }
+
+    fn clear(&mut self) {
+        for pc in self.marked.drain(..) {
+            self.visited[pc] = false;
+        }
+        self.list.clear();
+    }
+}
+
+impl Regex {
+    pub fn new(pattern: &str) -> Result<Self, RegexError> {
+        let mut parser = Parser { chars: pattern.chars().collect(), pos: 0, groups: 0, depth: 0, height: 0 };
+        let node = parser.parse()?;
+        let mut compiler = Compiler { program: vec![], classes: vec![] };
+        compiler.emit(Inst::Save(0))?;
+        compiler.compile(&node)?;
+        compiler.emit(Inst::Save(1))?;
+        compiler.emit(Inst::Match)?;
+        Ok(Regex { program: compiler.program, classes: compiler.classes, slots: 2 * (parser.groups + 1), dfa: Mutex::new(Dfa::default()) })
+    }
+
+    /// Number of the groups, the whole match included
+    pub fn captures_len(&self) -> usize {
+        self.slots / 2
+    }
+
+    fn consumes(&self, pc: usize, ch: char) -> bool {
+        match self.program[pc] {
+            Inst::Char(expected) => expected == ch,
+            Inst::Any => ch != '\n',
+            Inst::Class(idx) => self.classes[idx].contains(ch),
+            _ => false,
+        }
+    }
+
+    // the consuming states, `Match` and the unresolved `$` reachable from `seeds` without reading a character
+    fn closure(&self, seeds: &[usize], at_start: bool, at_end: bool, res: &mut Vec<usize>) {
+        let mut visited = vec![false; self.program.len()];
+        let mut stack: Vec<usize> = seeds.to_vec();
+        while let Some(pc) = stack.pop() {
+            if visited[pc] {
+                continue;
+            }
+            visited[pc] = true;
+            match self.program[pc] {
+                Inst::Split(first, second) => {
+                    stack.push(first);
+                    stack.push(second);
+                }
+                Inst::Jump(next) => stack.push(next),
+                Inst::Save(_) => stack.push(pc + 1),
+                Inst::AssertStart => {
+                    if at_start {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::AssertEnd if at_end => stack.push(pc + 1),
+                _ => res.push(pc),
+            }
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        let mut dfa = match self.dfa.lock() {
+            Ok(dfa) => dfa,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut pcs = vec![];
+        self.closure(&[0], true, false, &mut pcs);
+        pcs.sort_unstable();
+        pcs.dedup();
+        let mut state = dfa.state(pcs);
+        let mut at_start = true;
+        for ch in text.chars() {
+            if dfa.states[state].iter().any(|&pc| matches!(self.program[pc], Inst::Match)) {
+                return true;
+            }
+            state = match dfa.transitions.get(&(state, ch)) {
+                Some(&next) => next,
+                None => {
+                    let mut seeds: Vec<usize> = dfa.states[state].iter().filter(|&&pc| self.consumes(pc, ch)).map(|pc| pc + 1).collect();
+                    // unanchored search: a match can start at any position
+                    seeds.push(0);
+                    let mut pcs = vec![];
+                    self.closure(&seeds, false, false, &mut pcs);
+                    pcs.sort_unstable();
+                    pcs.dedup();
+                    let generation = dfa.generation;
+                    let next = dfa.state(pcs);
+                    // the cache could be dropped, then the current state is gone
+                    if generation == dfa.generation {
+                        dfa.transitions.insert((state, ch), next);
+                    }
+                    next
+                }
+            };
+            at_start = false;
+        }
+        let mut pcs = vec![];
+        self.closure(&dfa.states[state], at_start, true, &mut pcs);
+        pcs.iter().any(|&pc| matches!(self.program[pc], Inst::Match))
+    }
+
+    fn add_thread(&self, threads: &mut Threads, pc: usize, slots: &mut [Option<usize>], pos: usize, len: usize) {
+        let mut stack = vec![Frame::Pc(pc)];
+        while let Some(frame) = stack.pop() {
+            let pc = match frame {
+                Frame::Restore(slot, value) => {
+                    slots[slot] = value;
+                    continue;
+                }
+                Frame::Pc(pc) => pc,
+            };
+            if threads.visited[pc] {
+                continue;
+            }
+            threads.visited[pc] = true;
+            threads.marked.push(pc);
+            match self.program[pc] {
+                Inst::Split(first, second) => {
+                    stack.push(Frame::Pc(second));
+                    stack.push(Frame::Pc(first));
+                }
+                Inst::Jump(next) => stack.push(Frame::Pc(next)),
+                Inst::Save(slot) => {
+                    stack.push(Frame::Restore(slot, slots[slot]));
+                    slots[slot] = Some(pos);
+                    stack.push(Frame::Pc(pc + 1));
+                }
+                Inst::AssertStart => {
+                    if pos == 0 {
+                        stack.push(Frame::Pc(pc + 1));
+                    }
+                }
+                Inst::AssertEnd => {
+                    if pos == len {
+                        stack.push(Frame::Pc(pc + 1));
+                    }
+                }
+                _ => threads.list.push((pc, slots.to_vec())),
+            }
+        }
+    }
+
+    // Pike VM: the leftmost-first match starting at `start` or later
+    fn pike(&self, text: &str, start: usize) -> Option<Vec<Option<usize>>> {
+        let len = text.len();
+        let mut current = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        let mut matched = None;
+        let mut chars = text[start..].chars();
+        let mut pos = start;
+        loop {
+            if matched.is_none() {
+                let mut slots = vec![None; self.slots];
+                self.add_thread(&mut current, 0, &mut slots, pos, len);
+            }
+            if current.list.is_empty() {
+                break;
+            }
+            let ch = chars.next();
+            let next_pos = pos + ch.map_or(0, |ch| ch.len_utf8());
+            for (pc, slots) in current.list.iter_mut() {
+                if let Inst::Match = self.program[*pc] {
+                    matched = Some(slots.clone());
+                    // the threads after this one have the lower priority
+                    break;
+                }
+                if let Some(ch) = ch {
+                    if self.consumes(*pc, ch) {
+                        self.add_thread(&mut next, *pc + 1, slots, next_pos, len);
+                    }
+                }
+            }
+            current.clear();
+            std::mem::swap(&mut current, &mut next);
+            if ch.is_none() {
+                break;
+            }
+            pos = next_pos;
+        }
+        matched
+    }
+
+    /// Byte range of the leftmost-first match
+    pub fn find(&self, text: &str) -> Option<Range<usize>> {
+        self.pike(text, 0).map(|slots| slots[0].unwrap()..slots[1].unwrap())
+    }
+
+    /// All non-overlapping matches from left to right.
+    /// Every match starts a new search after the previous one, and a search may read far past the match it returns,
+    /// so the total time is quadratic in the worst case (e.g. `\w+x|\w` on a long word without `x`)
+    pub fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        let mut res = vec![];
+        let mut start = 0;
+        while start <= text.len() {
+            let slots = match self.pike(text, start) {
+                Some(slots) => slots,
+                None => break,
+            };
+            let (from, to) = (slots[0].unwrap(), slots[1].unwrap());
+            res.push(from..to);
+            start = if to > from {
+                to
+            } else {
+                match text[to..].chars().next() {
+                    Some(ch) => to + ch.len_utf8(),
+                    None => break,
+                }
+            };
+        }
+        res
+    }
+
+    /// Groups of the leftmost-first match
+    pub fn captures<'a>(&self, text: &'a str) -> Option<Captures<'a>> {
+        self.pike(text, 0).map(|slots| Captures { text, slots })
+    }
+}
+
+#[cfg(test)]
+fn check(pattern: &str, text: &str, expected: Option<Range<usize>>) {
+    let re = Regex::new(pattern).unwrap();
+    assert_eq!(re.find(text), expected, "{} {}", pattern, text);
+    assert_eq!(re.is_match(text), expected.is_some(), "{} {}", pattern, text);
+}
+
+#[test]
+fn test_regex_find() {
+    check("abc", "xxabcxx", Some(2..5));
+    check("abc", "ab", None);
+    check("", "abc", Some(0..0));
+    check("a|b|c", "xxc", Some(2..3));
+    check("a*", "bbb", Some(0..0));
+    check("a+", "baaab", Some(1..4));
+    check("a+?", "baaab", Some(1..2));
+    check("a??b", "ab", Some(0..2));
+    check("colou?r", "the color red", Some(4..9));
+    check("^abc", "xabc", None);
+    check("^abc$", "abc", Some(0..3));
+    check("abc$", "abcabc", Some(3..6));
+    check("^$", "", Some(0..0));
+    check("^$", "a", None);
+    check("a.c", "a\nc abc", Some(4..7));
+    check("[a-c]+", "xxbcaz", Some(2..5));
+    check("[^a-c]+", "abxyc", Some(2..4));
+    check("[]a]+", "x]a]", Some(1..4));
+    check("[a-]+", "x-a-", Some(1..4));
+    check(r"\d{3}-\d{4}", "call 555-1234 now", Some(5..13));
+    check(r"\w+@\w+\.com", "mail: joe@example.com", Some(6..21));
+    check(r"[\d\s]+", "ab 12 3c", Some(2..7));
+    check(r"[^\D]+", "ab12c", Some(2..4));
+    check(r"\S+", "  xy ", Some(2..4));
+    check("a{3}", "aaaa", Some(0..3));
+    check("a{2,}", "a aaaa", Some(2..6));
+    check("a{1,2}?", "aaa", Some(0..1));
+    check("(a|ab)(c|bcd)(d*)", "abcd", Some(0..4));
+    check("(?:ab)+", "ababab", Some(0..6));
+    check("(a*)*b", "aaab", Some(0..4));
+    check("(a*)+$", "aab", Some(3..3));
+    check("é+", "caféé!", Some(3..7));
+    check(r"\.\*", "a.*b", Some(1..3));
+}
+
+#[test]
+fn test_regex_captures() {
+    let re = Regex::new(r"(\w+)=(\d+)?(;)?").unwrap();
+    assert_eq!(re.captures_len(), 4);
+    let caps = re.captures("key=;").unwrap();
+    assert_eq!(caps.get(1), Some("key"));
+    assert_eq!(caps.get(2), None);
+    assert_eq!(caps.get(3), Some(";"));
+    assert_eq!(caps.len(), 4);
+
+    let re = Regex::new("(a|ab)(c|bcd)(d*)").unwrap();
+    let caps = re.captures("abcd").unwrap();
+    assert_eq!((caps.get(1), caps.get(2), caps.get(3)), (Some("a"), Some("bcd"), Some("")));
+
+    let re = Regex::new("(a)+").unwrap();
+    assert_eq!(re.captures("aaa").unwrap().range(1), Some(2..3));
+    assert!(Regex::new("x").unwrap().captures("abc").is_none());
+}
+
+#[test]
+fn test_regex_find_all() {
+    let re = Regex::new(r"\d+").unwrap();
+    assert_eq!(re.find_all("a1b22c333"), vec![1..2, 3..5, 6..9]);
+    let re = Regex::new("a*").unwrap();
+    assert_eq!(re.find_all("baaé"), vec![0..0, 1..3, 3..3, 5..5]);
+}
+
+#[test]
+fn test_regex_errors() {
+    assert_eq!(Regex::new("(ab").err(), Some(RegexError::UnexpectedEnd));
+    assert_eq!(Regex::new("ab)").err(), Some(RegexError::UnmatchedParen(2)));
+    assert_eq!(Regex::new("*a").err(), Some(RegexError::NothingToRepeat(0)));
+    assert_eq!(Regex::new("a|+").err(), Some(RegexError::NothingToRepeat(2)));
+    assert_eq!(Regex::new("a{x}").err(), Some(RegexError::InvalidRepeat(1)));
+    assert_eq!(Regex::new("a{3,2}").err(), Some(RegexError::InvalidRepeat(1)));
+    let nested = "(".repeat(200_000) + &")".repeat(200_000);
+    assert_eq!(Regex::new(&nested).err(), Some(RegexError::TooDeep(250)));
+    assert_eq!(Regex::new(&("a".to_string() + &"*".repeat(200_000))).err(), Some(RegexError::TooDeep(251)));
+    assert_eq!(Regex::new(&"(a*)*".repeat(2).repeat(100)).err(), None);
+    assert_eq!(Regex::new(&("(a*)*".repeat(125) + "(((a*)*)*)*")).err(), None);
+    assert_eq!(Regex::new(&("(".repeat(200) + &")*".repeat(200))).err(), Some(RegexError::TooDeep(74)));
+    assert!(Regex::new(&("(".repeat(250) + &")".repeat(250))).unwrap().is_match(""));
+    assert_eq!(Regex::new("[z-a]").err(), Some(RegexError::InvalidClass(0)));
+    assert_eq!(Regex::new("[ab").err(), Some(RegexError::UnexpectedEnd));
+    assert_eq!(Regex::new(r"\q").err(), Some(RegexError::InvalidEscape(0)));
+    assert_eq!(Regex::new("a{1000}{1000}").err(), Some(RegexError::TooBig));
+    assert_eq!(Regex::new("a{1001}").err(), Some(RegexError::TooBig));
+    assert_eq!(Regex::new("a{0,1001}").err(), Some(RegexError::TooBig));
+    assert_eq!(Regex::new("(?:){4294967295}").err(), Some(RegexError::TooBig));
+    let re = Regex::new(&("(?:".repeat(50) + &"){1000}".repeat(50))).unwrap();
+    assert_eq!(re.find("ab"), Some(0..0));
+    let re = Regex::new("(){3}a").unwrap();
+    assert_eq!(re.captures("xa").unwrap().range(1), Some(1..1));
+}
+
+#[test]
+fn test_regex_linear_time() {
+    // exponential for the backtracking engines
+    let n = 25;
+    let pattern = "a?".repeat(n) + &"a".repeat(n);
+    let re = Regex::new(&pattern).unwrap();
+    let text = "a".repeat(n);
+    assert!(re.is_match(&text));
+    assert_eq!(re.find(&text), Some(0..n));
+    let re = Regex::new("(x+x+)+y").unwrap();
+    let text = "x".repeat(5000);
+    assert!(!re.is_match(&text));
+    assert_eq!(re.find(&text), None);
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use rand::Rng;
+
+const MOD1: u64 = 1_000_000_007;
+const MOD2: u64 = 998_244_353;
+
+/// Polynomial rolling hash of a text modulo two primes.
+/// The bases are chosen randomly, so the collisions can not be prepared in advance.
+/// Two hashes can be compared only if they have the same bases (see `build_with_bases`).
+///```
+/// use librualg::string::RollingHash;
+///
+/// let text = RollingHash::build("abracadabra");
+/// assert_eq!(text.substring_hash(0, 4), text.substring_hash(7, 11));
+/// assert_ne!(text.substring_hash(0, 4), text.substring_hash(1, 5));
+///
+/// let other = RollingHash::build_with_bases("cadabra!", text.bases());
+/// assert!(text.substrings_equal(4, 11, &other, 0, 7));
+/// assert_eq!(text.longest_common_prefix(4, &other, 0), 7);
+/// assert_eq!(other.hash_of("dab"), other.substring_hash(2, 5));
+/// ```
+#[derive(Clone)]
+pub struct RollingHash {
+    bases: (u64, u64),
+    prefix: Vec<(u64, u64)>,
+    powers: Vec<(u64, u64)>,
+}
+
+impl RollingHash {
+    /// Hash with random bases
+    pub fn build(text: &str) -> Self {
+        let mut rng = rand::thread_rng();
+        let bases = (rng.gen_range(257, MOD1 - 1), rng.gen_range(257, MOD2 - 1));
+        RollingHash::build_with_bases(text, bases)
+    }
+
+    /// Hash with the given bases, e.g. the bases of another hash to compare the substrings of two texts
+    pub fn build_with_bases(text: &str, bases: (u64, u64)) -> Self {
+        let bases = (bases.0 % MOD1, bases.1 % MOD2);
+        let mut prefix = Vec::with_capacity(text.len() + 1);
+        let mut powers = Vec::with_capacity(text.len() + 1);
+        prefix.push((0, 0));
+        powers.push((1, 1));
+        for (i, ch) in text.bytes().enumerate() {
+            let (h1, h2) = prefix[i];
+            let (p1, p2) = powers[i];
+            prefix.push(((h1 * bases.0 + ch as u64 + 1) % MOD1, (h2 * bases.1 + ch as u64 + 1) % MOD2));
+            powers.push((p1 * bases.0 % MOD1, p2 * bases.1 % MOD2));
+        }
+        RollingHash { bases, prefix, powers }
+    }
+
+    pub fn bases(&self) -> (u64, u64) {
+        self.bases
+    }
+
+    /// Length of the text
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hash of the substring `text[l..r]` in O(1)
+    pub fn substring_hash(&self, l: usize, r: usize) -> (u64, u64) {
+        assert!(l <= r && r <= self.len(), "substring {}..{} is out of the text", l, r);
+        let (p1, p2) = self.powers[r - l];
+        (
+            (self.prefix[r].0 + MOD1 * MOD1 - self.prefix[l].0 * p1) % MOD1,
+            (self.prefix[r].1 + MOD2 * MOD2 - self.prefix[l].1 * p2) % MOD2,
+        )
+    }
+
+    /// Hash of a separate string with the bases of this hash
+    pub fn hash_of(&self, s: &str) -> (u64, u64) {
+        s.bytes().fold((0, 0), |(h1, h2), ch| ((h1 * self.bases.0 + ch as u64 + 1) % MOD1, (h2 * self.bases.1 + ch as u64 + 1) % MOD2))
+    }
+
+    /// Compares `text[l..r]` with `other_text[other_l..other_r]` by the hashes
+    pub fn substrings_equal(&self, l: usize, r: usize, other: &RollingHash, other_l: usize, other_r: usize) -> bool {
+        assert_eq!(self.bases, other.bases, "hashes with different bases are not comparable");
+        r - l == other_r - other_l && self.substring_hash(l, r) == other.substring_hash(other_l, other_r)
+    }
+
+    /// Length of the longest common prefix of `text[i..]` and `other_text[j..]` (binary search by the hashes)
+    pub fn longest_common_prefix(&self, i: usize, other: &RollingHash, j: usize) -> usize {
+        let (mut l, mut r) = (0, (self.len() - i).min(other.len() - j));
+        while l < r {
+            let mid = r - (r - l) / 2;
+            if self.substrings_equal(i, i + mid, other, j, j + mid) {
+                l = mid;
+            } else {
+                r = mid - 1;
+            }
+        }
+        l
+    }
+}
+
+/// Rabin-Karp search of several patterns.
+/// Returns the pairs (position in the text, index of the pattern) in ascending order.
+/// The candidates found by the hashes are checked, so there are no false positives.
+///```
+/// use librualg::string::rabin_karp;
+///
+/// assert_eq!(rabin_karp("abracadabra", &["abra", "cad", "ra"]), vec![(0, 0), (2, 2), (4, 1), (7, 0), (9, 2)]);
+/// ```
+pub fn rabin_karp(text: &str, patterns: &[&str]) -> Vec<(usize, usize)> {
+    let hash = RollingHash::build(text);
+    // length -> hash -> patterns
+    let mut groups: BTreeMap<usize, BTreeMap<(u64, u64), Vec<usize>>> = BTreeMap::new();
+    for (idx, pattern) in patterns.iter().enumerate() {
+        if !pattern.is_empty() && pattern.len() <= text.len() {
+            groups.entry(pattern.len()).or_default().entry(hash.hash_of(pattern)).or_default().push(idx);
+        }
+    }
+    let mut res = vec![];
+    for (len, hashes) in groups {
+        for pos in 0..=text.len() - len {
+            if let Some(candidates) = hashes.get(&hash.substring_hash(pos, pos + len)) {
+                for idx in candidates {
+                    if text.as_bytes()[pos..pos + len] == *patterns[*idx].as_bytes() {
+                        res.push((pos, *idx));
+                    }
+                }
+            }
+        }
+    }
+    res.sort_unstable();
+    res
+}
+
+#[test]
+fn test_rolling_hash() {
+    let text = "abacabadabacaba";
+    let hash = RollingHash::build(text);
+    assert_eq!(hash.len(), 15);
+    for l1 in 0..=text.len() {
+        for r1 in l1..=text.len() {
+            for l2 in 0..=text.len() - (r1 - l1) {
+                let r2 = l2 + r1 - l1;
+                assert_eq!(hash.substring_hash(l1, r1) == hash.substring_hash(l2, r2), text[l1..r1] == text[l2..r2]);
+            }
+            assert_eq!(hash.hash_of(&text[l1..r1]), hash.substring_hash(l1, r1));
+        }
+    }
+    let other = RollingHash::build_with_bases("xxabadaba", hash.bases());
+    assert!(other.substrings_equal(2, 9, &hash, 4, 11));
+    assert!(!other.substrings_equal(2, 9, &hash, 4, 10));
+    assert_eq!(hash.longest_common_prefix(4, &other, 2), 7);
+    assert_eq!(hash.longest_common_prefix(0, &other, 0), 0);
+    assert_eq!(hash.longest_common_prefix(15, &other, 9), 0);
+    assert!(RollingHash::build("").is_empty());
+}
+
+#[test]
+fn test_rabin_karp() {
+    let text = "she sells sea shells by the sea shore";
+    let patterns = ["sea", "she", "shells", "", "s", "shore!"];
+    let mut expected = vec![];
+    for (idx, pattern) in patterns.iter().enumerate() {
+        if !pattern.is_empty() {
+            for pos in crate::string::kmp(text, pattern) {
+                expected.push((pos, idx));
+            }
+        }
+    }
+    expected.sort_unstable();
+    assert_eq!(rabin_karp(text, &patterns), expected);
+    assert_eq!(rabin_karp("", &["a"]), vec![]);
+}
@@ -0,0 +1,252 @@
+use crate::sparse_table::SparseTableMin;
+
+/// Suffix array in linear time (SA-IS algorithm).
+/// Unlike `suffix_array` the text does not need a sentinel: the suffixes are sorted as usual,
+/// a proper prefix goes before the longer suffix.
+///```
+/// use librualg::string::suffix_array_sais;
+///
+/// assert_eq!(suffix_array_sais("ababba"), vec![5, 0, 2, 4, 1, 3]);
+/// assert_eq!(suffix_array_sais("ababba$"), vec![6, 5, 0, 2, 4, 1, 3]);
+/// ```
+pub fn suffix_array_sais(src: &str) -> Vec<usize> {
+    sa_is(src.as_bytes(), 255)
+}
+
+/// Induced sorting over the alphabet `0..=upper`: the bytes of the text at the top level,
+/// the names of the LMS substrings in the recursion
+pub fn sa_is<T: Copy + Ord + Into<usize>>(s: &[T], upper: usize) -> Vec<usize> {
+    let n = s.len();
+    match n {
+        0 => return vec![],
+        1 => return vec![0],
+        2 => return if s[0] < s[1] { vec![0, 1] } else { vec![1, 0] },
+        _ => {}
+    }
+    let mut sa = vec![0; n];
+    let mut ls = vec![false; n];
+    for i in (0..n - 1).rev() {
+        ls[i] = if s[i] == s[i + 1] { ls[i + 1] } else { s[i] < s[i + 1] };
+    }
+    let mut sum_l = vec![0; upper + 1];
+    let mut sum_s = vec![0; upper + 1];
+    for i in 0..n {
+        if !ls[i] {
+            sum_s[s[i].into()] += 1;
+        } else {
+            sum_l[s[i].into() + 1] += 1;
+        }
+    }
+    for i in 0..=upper {
+        sum_s[i] += sum_l[i];
+        if i < upper {
+            sum_l[i + 1] += sum_s[i];
+        }
+    }
+
+    // `usize::MAX` marks an empty cell
+    let induce = |lms: &[usize], sa: &mut Vec<usize>| {
+        for item in sa.iter_mut() {
+            *item = usize::MAX;
+        }
+        let mut buf = sum_s.clone();
+        for d in lms {
+            if *d == n {
+                continue;
+            }
+            sa[buf[s[*d].into()]] = *d;
+            buf[s[*d].into()] += 1;
+        }
+        buf.copy_from_slice(&sum_l);
+        sa[buf[s[n - 1].into()]] = n - 1;
+        buf[s[n - 1].into()] += 1;
+        for i in 0..n {
+            let v = sa[i];
+            if v != usize::MAX && v >= 1 && !ls[v - 1] {
+                sa[buf[s[v - 1].into()]] = v - 1;
+                buf[s[v - 1].into()] += 1;
+            }
+        }
+        buf.copy_from_slice(&sum_l);
+        for i in (0..n).rev() {
+            let v = sa[i];
+            if v != usize::MAX && v >= 1 && ls[v - 1] {
+                buf[s[v - 1].into() + 1] -= 1;
+                sa[buf[s[v - 1].into() + 1]] = v - 1;
+            }
+        }
+    };
+
+    // lms_map[i] is the number of the LMS position i
+    let mut lms_map = vec![usize::MAX; n + 1];
+    let mut m = 0;
+    for i in 1..n {
+        if !ls[i - 1] && ls[i] {
+            lms_map[i] = m;
+            m += 1;
+        }
+    }
+    let lms: Vec<usize> = (1..n).filter(|i| !ls[i - 1] && ls[*i]).collect();
+    induce(&lms, &mut sa);
+
+    if m > 0 {
+        let sorted_lms: Vec<usize> = sa.iter().cloned().filter(|v| lms_map[*v] != usize::MAX).collect();
+        let mut rec_s = vec![0; m];
+        let mut rec_upper = 0;
+        rec_s[lms_map[sorted_lms[0]]] = 0;
+        for i in 1..m {
+            let mut l = sorted_lms[i - 1];
+            let mut r = sorted_lms[i];
+            let end_l = if lms_map[l] + 1 < m { lms[lms_map[l] + 1] } else { n };
+            let end_r = if lms_map[r] + 1 < m { lms[lms_map[r] + 1] } else { n };
+            let mut same = true;
+            if end_l - l != end_r - r {
+                same = false;
+            } else {
+                while l < end_l {
+                    if s[l] != s[r] {
+                        break;
+                    }
+                    l += 1;
+                    r += 1;
+                }
+                if l == n || s[l] != s[r] {
+                    same = false;
+                }
+            }
+            if !same {
+                rec_upper += 1;
+            }
+            rec_s[lms_map[sorted_lms[i]]] = rec_upper;
+        }
+        let rec_sa = sa_is(&rec_s, rec_upper);
+        let sorted_lms: Vec<usize> = rec_sa.iter().map(|i| lms[*i]).collect();
+        induce(&sorted_lms, &mut sa);
+    }
+    sa
+}
+
+/// LCP array (Kasai's algorithm): `lcp[i]` is the length of the longest common prefix
+/// of the suffixes `suffix_array[i - 1]` and `suffix_array[i]`, `lcp[0] = 0`
+///```
+/// use librualg::string::{suffix_array_sais, lcp_array};
+///
+/// let sa = suffix_array_sais("banana");
+/// assert_eq!(sa, vec![5, 3, 1, 0, 4, 2]);
+/// assert_eq!(lcp_array("banana", &sa), vec![0, 1, 3, 0, 0, 2]);
+/// ```
+pub fn lcp_array(src: &str, suffix_array: &[usize]) -> Vec<usize> {
+    let bytes = src.as_bytes();
+    let n = bytes.len();
+    let mut rank = vec![0; n];
+    for (i, item) in suffix_array.iter().enumerate() {
+        rank[*item] = i;
+    }
+    let mut lcp = vec![0; n];
+    let mut k: usize = 0;
+    for i in 0..n {
+        if rank[i] == 0 {
+            k = 0;
+            continue;
+        }
+        let j = suffix_array[rank[i] - 1];
+        while i + k < n && j + k < n && bytes[i + k] == bytes[j + k] {
+            k += 1;
+        }
+        lcp[rank[i]] = k;
+        k = k.saturating_sub(1);
+    }
+    lcp
+}
+
+/// Suffix array with the LCP array and a sparse table over it:
+/// the longest common prefix of any two suffixes in O(1)
+///```
+/// use librualg::string::LcpTable;
+///
+/// let table = LcpTable::build("ababba");
+/// assert_eq!(table.suffix_array(), &[5, 0, 2, 4, 1, 3]);
+/// assert_eq!(table.lcp(0, 2), Some(2));
+/// assert_eq!(table.lcp(1, 4), Some(2));
+/// assert_eq!(table.lcp(3, 3), Some(3));
+/// assert_eq!(table.lcp(1, 6), None);
+/// ```
+pub struct LcpTable {
+    suffix_array: Vec<usize>,
+    rank: Vec<usize>,
+    lcp: Vec<usize>,
+    table: SparseTableMin<usize>,
+}
+
+impl LcpTable {
+    pub fn build(src: &str) -> Self {
+        let suffix_array = suffix_array_sais(src);
+        let lcp = lcp_array(src, &suffix_array);
+        let mut rank = vec![0; suffix_array.len()];
+        for (i, item) in suffix_array.iter().enumerate() {
+            rank[*item] = i;
+        }
+        let table = SparseTableMin::build(&lcp);
+        LcpTable { suffix_array, rank, lcp, table }
+    }
+
+    pub fn suffix_array(&self) -> &[usize] {
+        &self.suffix_array
+    }
+
+    pub fn lcp_array(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// Longest common prefix of the suffixes starting at the positions `i` and `j`
+    pub fn lcp(&self, i: usize, j: usize) -> Option<usize> {
+        let n = self.suffix_array.len();
+        if i >= n || j >= n {
+            return None;
+        }
+        if i == j {
+            return Some(n - i);
+        }
+        let (a, b) = if self.rank[i] < self.rank[j] { (self.rank[i], self.rank[j]) } else { (self.rank[j], self.rank[i]) };
+        Some(self.table.query(a + 1, b))
+    }
+}
+
+#[test]
+fn test_suffix_array_sais() {
+    use crate::string::suffix_array;
+
+    let texts = ["", "a", "ab", "ba", "aaaaaaa", "mississippi", "abracadabra", "ababba$", "bababa$", "zyxwvutsrqponmlkjihgfedcba"];
+    for text in &texts {
+        let mut expected: Vec<usize> = (0..text.len()).collect();
+        expected.sort_by(|a, b| text[*a..].cmp(&text[*b..]));
+        assert_eq!(suffix_array_sais(text), expected);
+    }
+    assert_eq!(suffix_array_sais("ababba$"), suffix_array("ababba$").0);
+
+    use rand::{Rng, SeedableRng, StdRng};
+    let mut rng = StdRng::from_seed(&[3]);
+    for _ in 0..200 {
+        let len = rng.gen_range(0, 60);
+        let alphabet = rng.gen_range(1, 4);
+        let text: String = (0..len).map(|_| (b'a' + rng.gen_range(0, alphabet)) as char).collect();
+        let mut expected: Vec<usize> = (0..text.len()).collect();
+        expected.sort_by(|a, b| text[*a..].cmp(&text[*b..]));
+        assert_eq!(suffix_array_sais(&text), expected);
+    }
+}
+
+#[test]
+fn test_lcp_table() {
+    let text = "abracadabraabracadabra";
+    let table = LcpTable::build(text);
+    for i in 0..text.len() {
+        for j in 0..text.len() {
+            let expected = text[i..].bytes().zip(text[j..].bytes()).take_while(|(a, b)| a == b).count();
+            assert_eq!(table.lcp(i, j), Some(expected));
+        }
+    }
+    assert_eq!(table.lcp(0, text.len()), None);
+    assert_eq!(lcp_array("", &[]), Vec::<usize>::new());
+    assert_eq!(LcpTable::build("").lcp(0, 0), None);
+}
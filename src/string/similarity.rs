@@ -0,0 +1,179 @@
+use std::cmp::{min, max};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Jaro similarity of two strings (over `char`s), 1.0 for equal strings and 0.0 for strings without common characters
+///```
+/// use librualg::string::jaro;
+///
+/// assert!((jaro("MARTHA", "MARHTA") - 0.9444).abs() < 1e-4);
+/// assert!((jaro("DIXON", "DICKSONX") - 0.7667).abs() < 1e-4);
+/// assert_eq!(jaro("abc", "xyz"), 0.0);
+/// ```
+pub fn jaro(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let window = (max(a.len(), b.len()) / 2).saturating_sub(1);
+    let mut used = vec![false; b.len()];
+    let mut matched_a = vec![];
+    for (i, ch) in a.iter().enumerate() {
+        let from = i.saturating_sub(window);
+        let to = min(i + window + 1, b.len());
+        for j in from..to {
+            if !used[j] && b[j] == *ch {
+                used[j] = true;
+                matched_a.push(*ch);
+                break;
+            }
+        }
+    }
+    if matched_a.is_empty() {
+        return 0.0;
+    }
+    let matched_b = b.iter().zip(used.iter()).filter(|(_, used)| **used).map(|(ch, _)| *ch);
+    let transpositions = matched_a.iter().zip(matched_b).filter(|(x, y)| **x != *y).count() / 2;
+    let m = matched_a.len() as f64;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0
+}
+
+/// Jaro–Winkler similarity: Jaro similarity boosted by the common prefix (up to 4 characters, scaling factor 0.1)
+///```
+/// use librualg::string::jaro_winkler;
+///
+/// assert!((jaro_winkler("MARTHA", "MARHTA") - 0.9611).abs() < 1e-4);
+/// assert!((jaro_winkler("DIXON", "DICKSONX") - 0.8133).abs() < 1e-4);
+/// ```
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let sim = jaro(a, b);
+    let prefix = a.chars().zip(b.chars()).take(4).take_while(|(x, y)| x == y).count();
+    sim + prefix as f64 * 0.1 * (1.0 - sim)
+}
+
+fn ngrams(s: &str, n: usize) -> BTreeSet<Vec<char>> {
+    let chars: Vec<char> = s.chars().collect();
+    chars.windows(n).map(|w| w.to_vec()).collect()
+}
+
+// (common, first, second) numbers of the distinct n-grams, None if both strings are shorter than n
+fn ngram_counts(a: &str, b: &str, n: usize) -> Option<(usize, usize, usize)> {
+    assert!(n > 0, "n-grams must be non-empty");
+    let (first, second) = (ngrams(a, n), ngrams(b, n));
+    if first.is_empty() && second.is_empty() {
+        return None;
+    }
+    Some((first.intersection(&second).count(), first.len(), second.len()))
+}
+
+/// Jaccard index of the sets of character n-grams.
+/// Strings shorter than `n` are similar only if they are equal.
+///```
+/// use librualg::string::ngram_jaccard;
+///
+/// assert_eq!(ngram_jaccard("night", "nacht", 2), 1.0 / 7.0);
+/// assert_eq!(ngram_jaccard("abc", "abc", 2), 1.0);
+/// ```
+pub fn ngram_jaccard(a: &str, b: &str, n: usize) -> f64 {
+    match ngram_counts(a, b, n) {
+        Some((common, first, second)) => common as f64 / (first + second - common) as f64,
+        None => if a == b { 1.0 } else { 0.0 },
+    }
+}
+
+/// Sørensen–Dice coefficient of the sets of character n-grams
+///```
+/// use librualg::string::ngram_dice;
+///
+/// assert_eq!(ngram_dice("night", "nacht", 2), 0.25);
+/// ```
+pub fn ngram_dice(a: &str, b: &str, n: usize) -> f64 {
+    match ngram_counts(a, b, n) {
+        Some((common, first, second)) => 2.0 * common as f64 / (first + second) as f64,
+        None => if a == b { 1.0 } else { 0.0 },
+    }
+}
+
+/// Cosine similarity of the token (split by whitespace) frequency vectors
+///```
+/// use librualg::string::cosine_similarity;
+///
+/// assert!((cosine_similarity("john smith", "smith john") - 1.0).abs() < 1e-9);
+/// assert!((cosine_similarity("john smith", "john doe") - 0.5).abs() < 1e-9);
+/// assert_eq!(cosine_similarity("john", "jane"), 0.0);
+/// ```
+pub fn cosine_similarity(a: &str, b: &str) -> f64 {
+    let frequencies = |s: &str| {
+        let mut res: BTreeMap<String, usize> = BTreeMap::new();
+        for token in s.split_whitespace() {
+            *res.entry(token.to_string()).or_insert(0) += 1;
+        }
+        res
+    };
+    let (first, second) = (frequencies(a), frequencies(b));
+    if first.is_empty() && second.is_empty() {
+        return 1.0;
+    }
+    if first.is_empty() || second.is_empty() {
+        return 0.0;
+    }
+    let dot: usize = first.iter().map(|(token, cnt)| cnt * second.get(token).cloned().unwrap_or(0)).sum();
+    let norm = |v: &BTreeMap<String, usize>| (v.values().map(|cnt| cnt * cnt).sum::<usize>() as f64).sqrt();
+    (dot as f64 / (norm(&first) * norm(&second))).min(1.0)
+}
+
+/// Ratio `2 * lcs / (|a| + |b|)`, where `lcs` is the length of the longest common subsequence of the characters
+///```
+/// use librualg::string::lcs_ratio;
+///
+/// assert_eq!(lcs_ratio("ABCBDAB", "BDCABA"), 8.0 / 13.0);
+/// assert_eq!(lcs_ratio("", ""), 1.0);
+/// ```
+pub fn lcs_ratio(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    2.0 * crate::string::lcs_length(&a, &b) as f64 / (a.len() + b.len()) as f64
+}
+
+#[test]
+fn test_jaro() {
+    assert_eq!(jaro("", ""), 1.0);
+    assert_eq!(jaro("", "a"), 0.0);
+    assert_eq!(jaro("same", "same"), 1.0);
+    assert!((jaro("CRATE", "TRACE") - 0.7333).abs() < 1e-4);
+    assert!((jaro_winkler("DWAYNE", "DUANE") - 0.84).abs() < 1e-4);
+    assert!((jaro_winkler("JELLYFISH", "SMELLYFISH") - 0.8963).abs() < 1e-4);
+    assert_eq!(jaro_winkler("Zoë", "Zoë"), 1.0);
+    for (a, b) in &[("abc", "bca"), ("dixon", "dicksonx"), ("ab", "ba"), ("a", "aaaa")] {
+        let (x, y) = (jaro_winkler(a, b), jaro_winkler(b, a));
+        assert!((x - y).abs() < 1e-12);
+        assert!((0.0..=1.0).contains(&x));
+    }
+}
+
+#[test]
+fn test_ngram_similarity() {
+    assert_eq!(ngram_jaccard("a", "a", 3), 1.0);
+    assert_eq!(ngram_jaccard("a", "b", 3), 0.0);
+    assert_eq!(ngram_jaccard("ab", "abc", 3), 0.0);
+    assert_eq!(ngram_dice("Jürgen", "Jürgen", 2), 1.0);
+    assert_eq!(ngram_dice("aaaa", "aa", 2), 1.0);
+    assert_eq!(ngram_jaccard("abcd", "bcde", 2), 0.5);
+    assert_eq!(ngram_dice("abcd", "bcde", 2), 2.0 / 3.0);
+}
+
+#[test]
+fn test_cosine_and_lcs_ratio() {
+    assert_eq!(cosine_similarity("", "  "), 1.0);
+    assert_eq!(cosine_similarity("a", ""), 0.0);
+    assert!((cosine_similarity("a a b", "a b b") - 0.8).abs() < 1e-9);
+    assert_eq!(lcs_ratio("abc", ""), 0.0);
+    assert_eq!(lcs_ratio("abc", "abc"), 1.0);
+    assert_eq!(lcs_ratio("ñandú", "nandu"), 0.6);
+}
@@ -0,0 +1,203 @@
+use std::cmp::{max, Reverse};
+use std::collections::BTreeMap;
+use crate::binary_search::lower_bound_index;
+
+/// Longest common subsequence of the characters
+///```
+/// use librualg::string::lcs;
+///
+/// assert_eq!(lcs("ABCBDAB", "BDCABA").len(), 4);
+/// assert_eq!(lcs("AGGTAB", "GXTXAYB"), "GTAB");
+/// ```
+pub fn lcs(a: &str, b: &str) -> String {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    lcs_slice(&a, &b).iter().map(|&(i, _)| a[i]).collect()
+}
+
+/// Longest common subsequence in O(nm): the pairs of the matched positions `(i, j)`, `a[i] == b[j]`
+///```
+/// use librualg::string::lcs_slice;
+///
+/// assert_eq!(lcs_slice(&[1, 2, 3, 4], &[2, 4, 3]), vec![(1, 0), (3, 1)]);
+/// ```
+pub fn lcs_slice<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    // dp[i][j]: length of the lcs of a[i..] and b[j..]
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            dp[i][j] = if a[i] == b[j] { dp[i + 1][j + 1] + 1 } else { max(dp[i + 1][j], dp[i][j + 1]) };
+        }
+    }
+    let mut res = Vec::with_capacity(dp[0][0]);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            res.push((i, j));
+            i += 1;
+            j += 1;
+        } else if dp[i + 1][j] >= dp[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    res
+}
+
+/// Length of the longest common subsequence in O(nm) time and O(m) memory
+///```
+/// use librualg::string::lcs_length;
+///
+/// assert_eq!(lcs_length(b"ABCBDAB", b"BDCABA"), 4);
+/// ```
+pub fn lcs_length<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let mut prev = vec![0usize; b.len() + 1];
+    let mut cur = vec![0usize; b.len() + 1];
+    for x in a {
+        for (j, y) in b.iter().enumerate() {
+            cur[j + 1] = if x == y { prev[j] + 1 } else { max(prev[j + 1], cur[j]) };
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Longest common subsequence by Hunt–Szymanski in O((r + n) log n), where `r` is the number of the pairs
+/// of the equal elements. Fast when the sequences share few elements (lines of files, tokens).
+///```
+/// use librualg::string::hunt_szymanski;
+///
+/// let old = ["fn main() {", "    let x = 1;", "    println!(x);", "}"];
+/// let new = ["fn main() {", "    let x = 2;", "    println!(x);", "}"];
+/// assert_eq!(hunt_szymanski(&old, &new), vec![(0, 0), (2, 2), (3, 3)]);
+/// ```
+pub fn hunt_szymanski<T: Ord>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let mut positions: BTreeMap<&T, Vec<usize>> = BTreeMap::new();
+    for (j, item) in b.iter().enumerate().rev() {
+        positions.entry(item).or_default().push(j);
+    }
+    // the pairs with the equal elements, the positions in `b` are decreasing for every `i`,
+    // so a strictly increasing subsequence takes at most one pair for every `i`
+    let mut pairs = vec![];
+    for (i, item) in a.iter().enumerate() {
+        if let Some(js) = positions.get(item) {
+            pairs.extend(js.iter().map(|&j| (i, j)));
+        }
+    }
+    let js: Vec<usize> = pairs.iter().map(|&(_, j)| j).collect();
+    lis(&js).into_iter().map(|idx| pairs[idx]).collect()
+}
+
+/// Longest strictly increasing subsequence in O(n log n), returns the positions of its elements
+///```
+/// use librualg::string::lis;
+///
+/// let seq = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+/// let res = lis(&seq);
+/// assert_eq!(res.len(), 4);
+/// assert_eq!(res.iter().map(|&i| seq[i]).collect::<Vec<_>>(), vec![1, 2, 3, 5]);
+/// ```
+pub fn lis<T: Ord>(src: &[T]) -> Vec<usize> {
+    let keys: Vec<&T> = src.iter().collect();
+    increasing(&keys)
+}
+
+/// Longest strictly decreasing subsequence in O(n log n), returns the positions of its elements
+///```
+/// use librualg::string::lds;
+///
+/// let seq = [5, 2, 8, 6, 3, 6, 9, 7];
+/// assert_eq!(lds(&seq).iter().map(|&i| seq[i]).collect::<Vec<_>>(), vec![8, 6, 3]);
+/// ```
+pub fn lds<T: Ord>(src: &[T]) -> Vec<usize> {
+    let keys: Vec<Reverse<&T>> = src.iter().map(Reverse).collect();
+    increasing(&keys)
+}
+
+fn increasing<T: Ord + Copy>(src: &[T]) -> Vec<usize> {
+    // tails[k]: the smallest last element of an increasing subsequence of length k + 1
+    let mut tails: Vec<T> = vec![];
+    let mut tail_positions: Vec<usize> = vec![];
+    let mut prev = vec![None; src.len()];
+    for (idx, item) in src.iter().enumerate() {
+        let pos = lower_bound_index(&tails, item);
+        if pos > 0 {
+            prev[idx] = Some(tail_positions[pos - 1]);
+        }
+        if pos == tails.len() {
+            tails.push(*item);
+            tail_positions.push(idx);
+        } else {
+            tails[pos] = *item;
+            tail_positions[pos] = idx;
+        }
+    }
+    let mut res = vec![];
+    let mut curr = tail_positions.last().cloned();
+    while let Some(idx) = curr {
+        res.push(idx);
+        curr = prev[idx];
+    }
+    res.reverse();
+    res
+}
+
+#[cfg(test)]
+fn check_common(a: &[u8], b: &[u8], pairs: &[(usize, usize)]) {
+    for w in pairs.windows(2) {
+        assert!(w[0].0 < w[1].0 && w[0].1 < w[1].1);
+    }
+    for &(i, j) in pairs {
+        assert_eq!(a[i], b[j]);
+    }
+}
+
+#[test]
+fn test_lcs() {
+    use rand::{Rng, SeedableRng, StdRng};
+
+    let mut rng = StdRng::from_seed(&[48]);
+    for _ in 0..300 {
+        let a: Vec<u8> = (0..rng.gen_range(0, 15)).map(|_| rng.gen_range(0, 4)).collect();
+        let b: Vec<u8> = (0..rng.gen_range(0, 15)).map(|_| rng.gen_range(0, 4)).collect();
+        let expected = lcs_slice(&a, &b);
+        check_common(&a, &b, &expected);
+        let res = hunt_szymanski(&a, &b);
+        check_common(&a, &b, &res);
+        assert_eq!(res.len(), expected.len());
+        assert_eq!(lcs_length(&a, &b), expected.len());
+        // the number of changes of the optimal edit script with insertions and deletions
+        assert_eq!(crate::string::levenshtein_distance_slice(&a, &b, 1, 1, 2) as usize, a.len() + b.len() - 2 * res.len());
+    }
+    assert_eq!(lcs("", "abc"), "");
+    assert_eq!(lcs("héllo", "hallo wörld"), "hllo");
+}
+
+#[test]
+fn test_lis() {
+    use rand::{Rng, SeedableRng, StdRng};
+
+    let mut rng = StdRng::from_seed(&[49]);
+    for _ in 0..300 {
+        let seq: Vec<u8> = (0..rng.gen_range(0, 12)).map(|_| rng.gen_range(0, 6)).collect();
+        // brute force over the subsets
+        let (mut inc, mut dec) = (0, 0);
+        for mask in 0u32..1 << seq.len() {
+            let sub: Vec<u8> = (0..seq.len()).filter(|i| mask >> i & 1 == 1).map(|i| seq[i]).collect();
+            if sub.windows(2).all(|w| w[0] < w[1]) {
+                inc = max(inc, sub.len());
+            }
+            if sub.windows(2).all(|w| w[0] > w[1]) {
+                dec = max(dec, sub.len());
+            }
+        }
+        let res = lis(&seq);
+        assert_eq!(res.len(), inc);
+        assert!(res.windows(2).all(|w| w[0] < w[1] && seq[w[0]] < seq[w[1]]));
+        let res = lds(&seq);
+        assert_eq!(res.len(), dec);
+        assert!(res.windows(2).all(|w| w[0] < w[1] && seq[w[0]] > seq[w[1]]));
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+struct State {
+    len: usize,
+    link: Option<usize>,
+    next: BTreeMap<char, usize>,
+    // byte offset after the end of the first occurrence
+    first_pos: usize,
+    cnt: usize,
+}
+
+/// Suffix automaton: the minimal automaton accepting all substrings of the text.
+/// It has at most `2n` states and `3n` transitions.
+/// The automaton is built over `char`s, the positions are byte offsets.
+///```
+/// use librualg::string::SuffixAutomaton;
+///
+/// let sa = SuffixAutomaton::build("abcbc");
+/// assert!(sa.contains("cbc"));
+/// assert!(!sa.contains("cc"));
+/// assert_eq!(sa.count_distinct_substrings(), 12);
+/// assert_eq!(sa.count_occurrences("bc"), 2);
+/// assert_eq!(sa.first_occurrence("bc"), Some(1));
+/// assert_eq!(sa.longest_common_substring("xxcbcxx"), Some("cbc"));
+/// ```
+pub struct SuffixAutomaton {
+    states: Vec<State>,
+    // number of chars
+    length: usize,
+}
+
+impl SuffixAutomaton {
+    pub fn build(text: &str) -> Self {
+        let mut states = Vec::with_capacity(2 * text.len() + 1);
+        states.push(State { len: 0, link: None, next: BTreeMap::new(), first_pos: 0, cnt: 0 });
+        let mut last = 0;
+        let mut length = 0;
+        for (i, ch) in text.char_indices() {
+            let ch = &ch;
+            length += 1;
+            let curr = states.len();
+            states.push(State { len: states[last].len + 1, link: None, next: BTreeMap::new(), first_pos: i + ch.len_utf8(), cnt: 1 });
+            let mut p = Some(last);
+            while let Some(v) = p {
+                if states[v].next.contains_key(ch) {
+                    break;
+                }
+                states[v].next.insert(*ch, curr);
+                p = states[v].link;
+            }
+            match p {
+                None => states[curr].link = Some(0),
+                Some(v) => {
+                    let q = states[v].next[ch];
+                    if states[v].len + 1 == states[q].len {
+                        states[curr].link = Some(q);
+                    } else {
+                        let clone = states.len();
+                        let mut state = states[q].clone();
+                        state.len = states[v].len + 1;
+                        state.cnt = 0;
+                        states.push(state);
+                        let mut p = Some(v);
+                        while let Some(v) = p {
+                            if states[v].next.get(ch) != Some(&q) {
+                                break;
+                            }
+                            states[v].next.insert(*ch, clone);
+                            p = states[v].link;
+                        }
+                        states[q].link = Some(clone);
+                        states[curr].link = Some(clone);
+                    }
+                }
+            }
+            last = curr;
+        }
+
+        // the number of occurrences is the number of end positions: sum over the suffix link tree
+        let mut order: Vec<usize> = (1..states.len()).collect();
+        order.sort_by(|a, b| states[*b].len.cmp(&states[*a].len));
+        for v in order {
+            if let Some(link) = states[v].link {
+                states[link].cnt += states[v].cnt;
+            }
+        }
+        SuffixAutomaton { states, length }
+    }
+
+    fn walk(&self, p: &str) -> Option<usize> {
+        let mut v = 0;
+        for ch in p.chars() {
+            v = *self.states[v].next.get(&ch)?;
+        }
+        Some(v)
+    }
+
+    /// Checks whether the pattern is a substring of the text
+    pub fn contains(&self, p: &str) -> bool {
+        self.walk(p).is_some()
+    }
+
+    /// Number of distinct non-empty substrings (of `char`s) of the text
+    pub fn count_distinct_substrings(&self) -> usize {
+        self.states.iter().skip(1).map(|state| state.len - self.states[state.link.unwrap()].len).sum()
+    }
+
+    /// Number of occurrences of the pattern in the text (the occurrences may overlap)
+    pub fn count_occurrences(&self, p: &str) -> usize {
+        if p.is_empty() {
+            return self.length + 1;
+        }
+        self.walk(p).map_or(0, |v| self.states[v].cnt)
+    }
+
+    /// Position of the first occurrence of the pattern in the text
+    pub fn first_occurrence(&self, p: &str) -> Option<usize> {
+        if p.is_empty() {
+            return Some(0);
+        }
+        self.walk(p).map(|v| self.states[v].first_pos - p.len())
+    }
+
+    /// Longest (in `char`s) common substring of the text and `other` (the first one in `other` if there are several)
+    pub fn longest_common_substring<'a>(&self, other: &'a str) -> Option<&'a str> {
+        let offsets: Vec<usize> = other.char_indices().map(|(offset, _)| offset).chain(Some(other.len())).collect();
+        let mut v = 0;
+        let mut len = 0;
+        let mut best = (0, 0);
+        for (i, ch) in other.chars().enumerate() {
+            while v != 0 && !self.states[v].next.contains_key(&ch) {
+                v = self.states[v].link.unwrap();
+                len = self.states[v].len;
+            }
+            if let Some(to) = self.states[v].next.get(&ch) {
+                v = *to;
+                len += 1;
+            }
+            if len > best.1 {
+                best = (i + 1 - len, len);
+            }
+        }
+        if best.1 == 0 {
+            return None;
+        }
+        Some(&other[offsets[best.0]..offsets[best.0 + best.1]])
+    }
+}
+
+#[test]
+fn test_suffix_automaton() {
+    use crate::string::distinct_substrings;
+
+    for text in &["", "a", "aaaa", "abaaba", "abacabadabacaba", "mississippi"] {
+        let sa = SuffixAutomaton::build(text);
+        assert_eq!(sa.count_distinct_substrings(), distinct_substrings(text).len());
+        for i in 0..text.len() {
+            for j in i + 1..=text.len() {
+                let p = &text[i..j];
+                assert!(sa.contains(p));
+                assert_eq!(sa.count_occurrences(p), crate::string::kmp(text, p).len());
+                assert_eq!(sa.first_occurrence(p), crate::string::kmp_first(text, p));
+            }
+        }
+    }
+    let sa = SuffixAutomaton::build("mississippi");
+    assert!(!sa.contains("sis "));
+    assert_eq!(sa.count_occurrences("x"), 0);
+    assert_eq!(sa.first_occurrence("ppa"), None);
+    assert_eq!(sa.count_occurrences(""), 12);
+}
+
+#[test]
+fn test_suffix_automaton_common_substring() {
+    let sa = SuffixAutomaton::build("VOTEFORTHEGREATALBANIAFORYOU");
+    assert_eq!(sa.longest_common_substring("CHOOSETHEGREATALBANIANFUTURE"), Some("THEGREATALBANIA"));
+    let sa = SuffixAutomaton::build("aba");
+    assert_eq!(sa.longest_common_substring("cabdd"), Some("ab"));
+    assert_eq!(sa.longest_common_substring("xyz"), None);
+    assert_eq!(sa.longest_common_substring(""), None);
+    assert_eq!(SuffixAutomaton::build("").longest_common_substring("abc"), None);
+}
+
+#[test]
+fn test_suffix_automaton_unicode() {
+    let sa = SuffixAutomaton::build("привет, мир");
+    assert!(sa.contains("мир"));
+    assert!(!sa.contains("\u{e9}"));
+    assert_eq!(sa.first_occurrence("мир"), Some(14));
+    assert_eq!(sa.count_occurrences("и"), 2);
+    assert_eq!(sa.count_occurrences(""), 12);
+    assert_eq!(SuffixAutomaton::build("ёё").count_distinct_substrings(), 2);
+    // 'é' and 'è' share the first byte
+    let sa = SuffixAutomaton::build("café");
+    assert_eq!(sa.longest_common_substring("è"), None);
+    assert_eq!(sa.longest_common_substring("un cafè"), Some("caf"));
+}
@@ -0,0 +1,324 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+const LEAF_END: usize = usize::MAX;
+
+struct Node {
+    start: usize,
+    end: usize,
+    link: usize,
+    suffix: usize,
+    children: BTreeMap<u32, usize>,
+}
+
+/// Generalized suffix tree of several strings (Ukkonen's algorithm).
+/// The strings are joined with unique separators, so the suffixes of different strings never mix.
+/// The tree is built over `char`s, the positions are byte offsets and the substrings never split a code point.
+///```
+/// use librualg::string::SuffixTree;
+///
+/// let tree = SuffixTree::build(&["abcdecd", "xbcdy", "zcdw"]);
+/// assert!(tree.contains("dec"));
+/// assert_eq!(tree.find_all("cd"), vec![(0, 2), (0, 5), (1, 2), (2, 1)]);
+/// assert_eq!(tree.longest_common_substring(3), Some("cd"));
+/// assert_eq!(tree.longest_common_substring(2), Some("bcd"));
+/// assert_eq!(tree.longest_repeated_substring(), Some("bcd"));
+/// ```
+pub struct SuffixTree<'a> {
+    texts: Vec<&'a str>,
+    starts: Vec<usize>,
+    // byte offset of every symbol in its string, the separator has the length of the string
+    offsets: Vec<usize>,
+    symbols: Vec<u32>,
+    nodes: Vec<Node>,
+}
+
+impl<'a> SuffixTree<'a> {
+    pub fn build(texts: &[&'a str]) -> Self {
+        let mut symbols = vec![];
+        let mut starts = vec![];
+        let mut offsets = vec![];
+        for (idx, text) in texts.iter().enumerate() {
+            starts.push(symbols.len());
+            for (offset, ch) in text.char_indices() {
+                symbols.push(ch as u32);
+                offsets.push(offset);
+            }
+            // above the largest code point
+            symbols.push(0x110000 + idx as u32);
+            offsets.push(text.len());
+        }
+        let mut tree = SuffixTree { texts: texts.to_vec(), starts, offsets, symbols, nodes: vec![] };
+        tree.ukkonen();
+        tree
+    }
+
+    fn new_node(&mut self, start: usize, end: usize, suffix: usize) -> usize {
+        self.nodes.push(Node { start, end, link: 0, suffix, children: BTreeMap::new() });
+        self.nodes.len() - 1
+    }
+
+    fn edge_length(&self, node: usize, pos: usize) -> usize {
+        if self.nodes[node].end == LEAF_END {
+            pos + 1 - self.nodes[node].start
+        } else {
+            self.nodes[node].end - self.nodes[node].start
+        }
+    }
+
+    fn ukkonen(&mut self) {
+        let root = self.new_node(0, 0, LEAF_END);
+        let mut active_node = root;
+        let mut active_edge = 0;
+        let mut active_length = 0;
+        let mut remainder = 0;
+        for pos in 0..self.symbols.len() {
+            remainder += 1;
+            let mut last_new: Option<usize> = None;
+            while remainder > 0 {
+                if active_length == 0 {
+                    active_edge = pos;
+                }
+                let ch = self.symbols[active_edge];
+                let linked = match self.nodes[active_node].children.get(&ch).cloned() {
+                    None => {
+                        let leaf = self.new_node(pos, LEAF_END, pos + 1 - remainder);
+                        self.nodes[active_node].children.insert(ch, leaf);
+                        active_node
+                    }
+                    Some(next) => {
+                        let length = self.edge_length(next, pos);
+                        if active_length >= length {
+                            active_edge += length;
+                            active_length -= length;
+                            active_node = next;
+                            continue;
+                        }
+                        if self.symbols[self.nodes[next].start + active_length] == self.symbols[pos] {
+                            active_length += 1;
+                            if let Some(node) = last_new {
+                                self.nodes[node].link = active_node;
+                            }
+                            break;
+                        }
+                        let start = self.nodes[next].start;
+                        let split = self.new_node(start, start + active_length, LEAF_END);
+                        self.nodes[active_node].children.insert(ch, split);
+                        let leaf = self.new_node(pos, LEAF_END, pos + 1 - remainder);
+                        self.nodes[split].children.insert(self.symbols[pos], leaf);
+                        self.nodes[next].start += active_length;
+                        self.nodes[split].children.insert(self.symbols[start + active_length], next);
+                        split
+                    }
+                };
+                if let Some(node) = last_new {
+                    self.nodes[node].link = linked;
+                }
+                last_new = if linked != active_node { Some(linked) } else { None };
+                remainder -= 1;
+                if active_node == root && active_length > 0 {
+                    active_length -= 1;
+                    active_edge = pos + 1 - remainder;
+                } else if active_node != root {
+                    active_node = self.nodes[active_node].link;
+                }
+            }
+        }
+        let end = self.symbols.len();
+        for node in self.nodes.iter_mut() {
+            if node.end == LEAF_END {
+                node.end = end;
+            }
+        }
+    }
+
+    /// Node whose path starts with the pattern
+    fn locate(&self, p: &str) -> Option<usize> {
+        let p: Vec<u32> = p.chars().map(|ch| ch as u32).collect();
+        let mut node = 0;
+        let mut i = 0;
+        while i < p.len() {
+            node = *self.nodes[node].children.get(&p[i])?;
+            let mut pos = self.nodes[node].start;
+            while pos < self.nodes[node].end && i < p.len() {
+                if self.symbols[pos] != p[i] {
+                    return None;
+                }
+                pos += 1;
+                i += 1;
+            }
+        }
+        Some(node)
+    }
+
+    /// Index of the string for the position in the joined text
+    fn text_index(&self, pos: usize) -> usize {
+        match self.starts.binary_search(&pos) {
+            Ok(idx) => idx,
+            Err(idx) => idx - 1,
+        }
+    }
+
+    /// Index of the string and the byte offset in it for the position in the joined text
+    fn position(&self, pos: usize) -> (usize, usize) {
+        (self.text_index(pos), self.offsets[pos])
+    }
+
+    /// Substring of `len` symbols from the position in the joined text
+    fn substring(&self, pos: usize, len: usize) -> &'a str {
+        let idx = self.text_index(pos);
+        &self.texts[idx][self.offsets[pos]..self.offsets[pos + len]]
+    }
+
+    fn leaves(&self, node: usize) -> Vec<usize> {
+        let mut res = vec![];
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            if self.nodes[node].children.is_empty() {
+                res.push(self.nodes[node].suffix);
+            }
+            stack.extend(self.nodes[node].children.values());
+        }
+        res
+    }
+
+    /// Checks whether the pattern is a substring of one of the strings
+    pub fn contains(&self, p: &str) -> bool {
+        self.locate(p).is_some()
+    }
+
+    /// All occurrences of the pattern: pairs (index of the string, position in the string) in ascending order
+    pub fn find_all(&self, p: &str) -> Vec<(usize, usize)> {
+        let mut res: Vec<(usize, usize)> = match self.locate(p) {
+            Some(node) => self.leaves(node).into_iter().map(|pos| self.position(pos)).collect(),
+            None => vec![],
+        };
+        res.sort_unstable();
+        res
+    }
+
+    /// Longest substring that occurs in at least `k` of the strings
+    pub fn longest_common_substring(&self, k: usize) -> Option<&'a str> {
+        if k == 0 || k > self.texts.len() {
+            return None;
+        }
+        // string depth of the nodes and a leaf below every node
+        let mut depth = vec![0; self.nodes.len()];
+        let mut leaf = vec![0; self.nodes.len()];
+        let mut sets: Vec<BTreeSet<usize>> = (0..self.nodes.len()).map(|_| BTreeSet::new()).collect();
+        let mut order = vec![];
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            order.push(node);
+            for child in self.nodes[node].children.values() {
+                depth[*child] = depth[node] + self.nodes[*child].end - self.nodes[*child].start;
+                stack.push(*child);
+            }
+        }
+        let mut best = (0, 0);
+        for node in order.into_iter().rev() {
+            let length = if self.nodes[node].children.is_empty() {
+                let suffix = self.nodes[node].suffix;
+                leaf[node] = suffix;
+                let idx = self.text_index(suffix);
+                sets[node].insert(idx);
+                // the symbols before the separator
+                let end = self.starts.get(idx + 1).map(|start| start - 1).unwrap_or(self.symbols.len() - 1);
+                end - suffix
+            } else {
+                let mut set = BTreeSet::new();
+                for child in self.nodes[node].children.values() {
+                    let mut other = std::mem::take(&mut sets[*child]);
+                    if other.len() > set.len() {
+                        std::mem::swap(&mut set, &mut other);
+                    }
+                    set.extend(other);
+                    leaf[node] = leaf[*child];
+                }
+                sets[node] = set;
+                depth[node]
+            };
+            if length > best.0 && sets[node].len() >= k {
+                best = (length, leaf[node]);
+            }
+        }
+        if best.0 == 0 {
+            return None;
+        }
+        Some(self.substring(best.1, best.0))
+    }
+
+    /// Longest substring that occurs at least twice (in one string or in different strings)
+    pub fn longest_repeated_substring(&self) -> Option<&'a str> {
+        let mut best = (0, 0);
+        let mut stack = vec![(0, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            if depth > best.0 && !self.nodes[node].children.is_empty() {
+                best = (depth, node);
+            }
+            for child in self.nodes[node].children.values() {
+                stack.push((*child, depth + self.nodes[*child].end - self.nodes[*child].start));
+            }
+        }
+        if best.0 == 0 {
+            return None;
+        }
+        Some(self.substring(self.leaves(best.1)[0], best.0))
+    }
+}
+
+#[test]
+fn test_suffix_tree_find() {
+    let texts = ["mississippi", "abracadabra", "aaaaaa", "abcabxabcd", ""];
+    let tree = SuffixTree::build(&texts);
+    for (idx, text) in texts.iter().enumerate() {
+        for i in 0..text.len() {
+            for j in i + 1..=text.len() {
+                let p = &text[i..j];
+                assert!(tree.contains(p));
+                let mut expected = vec![];
+                for (other_idx, other) in texts.iter().enumerate() {
+                    for pos in crate::string::kmp(other, p) {
+                        expected.push((other_idx, pos));
+                    }
+                }
+                expected.sort_unstable();
+                let found = tree.find_all(p);
+                assert!(found.contains(&(idx, i)));
+                assert_eq!(found, expected);
+            }
+        }
+    }
+    assert!(!tree.contains("sss"));
+    assert!(tree.find_all("ippia").is_empty());
+    assert_eq!(tree.find_all("").len(), texts.iter().map(|text| text.len()).sum::<usize>() + texts.len());
+}
+
+#[test]
+fn test_suffix_tree_substrings() {
+    let tree = SuffixTree::build(&["mississippi"]);
+    assert_eq!(tree.longest_repeated_substring(), Some("issi"));
+    assert_eq!(tree.longest_common_substring(1), Some("mississippi"));
+    assert_eq!(tree.longest_common_substring(2), None);
+
+    let tree = SuffixTree::build(&["GATTACA", "TAGACCA", "ATACA"]);
+    assert_eq!(tree.longest_common_substring(3).map(|s| s.len()), Some(2));
+    assert_eq!(tree.longest_common_substring(2), Some("TACA"));
+
+    let tree = SuffixTree::build(&["abc", "def"]);
+    assert_eq!(tree.longest_common_substring(2), None);
+    assert_eq!(tree.longest_repeated_substring(), None);
+    assert_eq!(tree.longest_common_substring(0), None);
+}
+
+#[test]
+fn test_suffix_tree_unicode() {
+    // 'é' and 'è' share the first byte
+    let tree = SuffixTree::build(&["café", "cafè", "кафе"]);
+    assert_eq!(tree.longest_common_substring(2), Some("caf"));
+    assert_eq!(tree.longest_common_substring(3), None);
+    assert!(!tree.contains("\u{e9}\u{e8}"));
+    assert_eq!(tree.find_all("е"), vec![(2, 6)]);
+    assert_eq!(tree.find_all("f"), vec![(0, 2), (1, 2)]);
+    let tree = SuffixTree::build(&["日本語の日本"]);
+    assert_eq!(tree.longest_repeated_substring(), Some("日本"));
+}
@@ -8,4 +8,12 @@ fn binary_search() {
     assert_eq!(binary_search::upper_bound(&seq, &3), Some(3));
     assert_eq!(binary_search::lower_bound(&seq, &3), Some(2));
     assert_eq!(binary_search::lower_bound(&seq, &6), None);
-}
\ No newline at end of file
+}
+
+#[test]
+fn binary_search_index() {
+    let seq = [1, 2, 3, 3, 4, 5];
+    assert_eq!(binary_search::lower_bound_index(&seq, &3), 2);
+    assert_eq!(binary_search::upper_bound_index(&seq, &3), 4);
+    assert_eq!(binary_search::lower_bound_index(&seq, &6), 6);
+}
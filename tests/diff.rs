@@ -0,0 +1,25 @@
+use librualg::diff::{myers, patience, unified_diff, apply_patch, DiffOp};
+
+#[test]
+fn test_diff() {
+    let old: Vec<&str> = "a b c a b b a".split(' ').collect();
+    let new: Vec<&str> = "c b a b a c".split(' ').collect();
+    let ops = myers(&old, &new);
+    assert_eq!(ops.iter().filter(|op| !matches!(op, DiffOp::Equal(_, _))).count(), 5);
+    let ops = patience(&old, &new);
+    assert_eq!(ops.iter().filter(|op| matches!(op, DiffOp::Equal(_, _))).count(), 4);
+}
+
+#[test]
+fn test_config_patch() {
+    let old = "[server]\nhost = 0.0.0.0\nport = 80\nworkers = 4\n\n[db]\nurl = postgres://db/app\npool = 10\ntimeout = 30\n";
+    let new = "[server]\nhost = 0.0.0.0\nport = 443\nworkers = 4\ntls = true\n\n[db]\nurl = postgres://db/app\npool = 20\ntimeout = 30\n";
+    let patch = unified_diff("a/app.toml", "b/app.toml", old, new, 1);
+    assert_eq!(patch, "--- a/app.toml\n+++ b/app.toml\n\
+        @@ -2,4 +2,5 @@\n host = 0.0.0.0\n-port = 80\n+port = 443\n workers = 4\n+tls = true\n \n\
+        @@ -7,3 +8,3 @@\n url = postgres://db/app\n-pool = 10\n+pool = 20\n timeout = 30\n");
+    assert_eq!(apply_patch(old, &patch), Some(new.to_string()));
+    assert_eq!(apply_patch(new, &patch), None);
+    // a hunk line must start with ' ', '-' or '+'
+    assert_eq!(apply_patch("a\n", "@@ -1,1 +1,1 @@\néa\n"), None);
+}
@@ -1,6 +1,6 @@
 extern crate librualg;
 
-use librualg::graph::{Graph, GraphNum};
+use librualg::graph::{Graph, GraphNum, GraphGenerator};
 
 #[test]
 fn test_bfs() {
@@ -283,4 +283,110 @@ fn test_kruskal_num() {
     let tree = graph.kruskal();
     assert_eq!(vec![1, 2, 5, 7], tree.search_path(7, &tree.bfs(1)).unwrap());
     assert_eq!(vec![1, 2, 5, 3], tree.search_path(3, &tree.bfs(1)).unwrap());
-}
\ No newline at end of file
+}
+#[test]
+fn test_centrality() {
+    let mut graph = Graph::new();
+    graph.add_oriented_edge("core", "io", 1.0);
+    graph.add_oriented_edge("core", "fmt", 1.0);
+    graph.add_oriented_edge("io", "fmt", 1.0);
+    graph.add_oriented_edge("app", "core", 1.0);
+    graph.add_oriented_edge("app", "io", 1.0);
+
+    let ranks = graph.page_rank(0.85, 1e-9, 100);
+    assert!(ranks["fmt"] > ranks["io"]);
+    assert!(ranks["io"] > ranks["core"]);
+    assert!(ranks["core"] > ranks["app"]);
+
+    let values = graph.betweenness_centrality();
+    assert_eq!(values["core"], 0.5);
+    assert_eq!(values["io"], 0.5);
+    assert_eq!(values["app"], 0.0);
+
+    let values = graph.degree_centrality();
+    assert_eq!(values["app"], 2.0 / 3.0);
+    assert_eq!(values["fmt"], 0.0);
+}
+
+#[test]
+fn test_coloring() {
+    let mut graph = Graph::new();
+    let exams = [("math", "physics"), ("math", "chemistry"), ("physics", "chemistry"), ("chemistry", "biology"), ("history", "biology")];
+    for (a, b) in exams.iter() {
+        graph.add_oriented_edge(*a, *b, 0.0);
+    }
+    let colors = graph.dsatur_coloring();
+    for (a, b) in exams.iter() {
+        assert_ne!(colors[a], colors[b]);
+    }
+    assert_eq!(graph.chromatic_number(), 3);
+    assert!(!graph.is_bipartite());
+    assert_eq!(graph.maximum_clique(), vec!["chemistry", "math", "physics"]);
+    assert_eq!(graph.maximal_cliques().len(), 3);
+}
+
+#[test]
+fn test_dominator_tree() {
+    let mut graph = Graph::new();
+    graph.add_oriented_edge("entry", "loop", 0.0);
+    graph.add_oriented_edge("loop", "body", 0.0);
+    graph.add_oriented_edge("body", "loop", 0.0);
+    graph.add_oriented_edge("loop", "exit", 0.0);
+
+    let tree = graph.dominator_tree("entry");
+    assert_eq!(tree.immediate_dominator(&"exit"), Some("loop"));
+    assert_eq!(tree.immediate_dominator(&"entry"), None);
+    assert!(tree.dominates(&"loop", &"body"));
+    assert_eq!(tree.dominance_frontier(&"body"), vec!["loop"]);
+
+    let mut graph = GraphNum::new(4);
+    for v in 1..5 {
+        graph.add_vertex(v);
+    }
+    graph.add_oriented_edge(1, 2, 0.0);
+    graph.add_oriented_edge(1, 3, 0.0);
+    graph.add_oriented_edge(2, 4, 0.0);
+    graph.add_oriented_edge(3, 4, 0.0);
+    let tree = graph.dominator_tree(1);
+    assert_eq!(tree.immediate_dominator(&4), Some(1));
+    assert_eq!(tree.dominance_frontier(&2), vec![4]);
+}
+
+#[test]
+fn test_generators() {
+    let mut generator = GraphGenerator::with_weights(2021, 1.0, 100.0);
+    let graph: Graph<usize> = generator.gnp(40, 0.3);
+    let tree = graph.kruskal();
+    let other: Graph<usize> = GraphGenerator::with_weights(2021, 1.0, 100.0).gnp(40, 0.3);
+    assert_eq!(graph.dijkstra(0).1, other.dijkstra(0).1);
+    assert_eq!(tree.connected_components().len(), graph.connected_components().len());
+
+    let graph: GraphNum = GraphGenerator::new(3).hypercube(4);
+    assert_eq!(graph.dijkstra(0).1[15], Some(4.0));
+}
+
+#[test]
+fn test_isomorphism() {
+    let mut first = Graph::new();
+    let mut second = Graph::new();
+    for (a, b) in [(1, 2), (2, 3), (3, 4), (4, 1), (1, 3)].iter() {
+        first.add_oriented_edge(*a, *b, 1.0);
+        second.add_oriented_edge(*b * 10, *a * 10, 1.0);
+    }
+    assert!(first.is_isomorphic(&second));
+    second.add_oriented_edge(10, 20, 1.0);
+    assert!(!first.is_isomorphic(&second));
+
+    let mut pattern = Graph::new();
+    pattern.add_oriented_edge('x', 'y', 1.0);
+    pattern.add_oriented_edge('y', 'z', 1.0);
+    pattern.add_oriented_edge('x', 'z', 1.0);
+    let matches: Vec<_> = first.subgraph_isomorphisms(&pattern).collect();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0][&'x'], 1);
+    assert_eq!(matches[0][&'y'], 2);
+    assert_eq!(matches[0][&'z'], 3);
+
+    pattern.add_oriented_edge('z', 'x', 1.0);
+    assert_eq!(first.subgraph_isomorphisms(&pattern).count(), 0);
+}
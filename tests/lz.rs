@@ -0,0 +1,22 @@
+use librualg::lz::{Lz77, Lz78, Lzw};
+
+#[test]
+fn test_round_trip() {
+    let text = "temperature=21.5;humidity=40;temperature=21.6;humidity=41;temperature=21.6;humidity=41;";
+    let data = text.as_bytes();
+
+    let encoded = Lz77::new(64).encode(data);
+    assert!(encoded.len() < data.len());
+    assert_eq!(Lz77::decode(&encoded).unwrap(), data);
+
+    let encoded = Lz78::encode(data);
+    assert_eq!(Lz78::decode(&encoded).unwrap(), data);
+
+    let encoded = Lzw::encode(data);
+    assert!(encoded.len() < data.len());
+    assert_eq!(Lzw::decode(&encoded).unwrap(), data);
+
+    assert_eq!(Lz77::decode(&Lz77::new(16).encode(b"")), Some(vec![]));
+    assert_eq!(Lz78::decode(&Lz78::encode(b"")), Some(vec![]));
+    assert_eq!(Lzw::decode(&Lzw::encode(b"")), Some(vec![]));
+}
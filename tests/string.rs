@@ -126,3 +126,298 @@ fn test_aho_corasick() {
     assert_eq!(m, res);
 
 }
+
+#[test]
+fn test_suffix_automaton() {
+    let log = "GET /index.html 200\nGET /about.html 404\nPOST /index.html 200\n";
+    let sa = string::SuffixAutomaton::build(log);
+    assert!(sa.contains("/about.html"));
+    assert!(!sa.contains("DELETE"));
+    assert_eq!(sa.count_occurrences("index.html 200"), 2);
+    assert_eq!(sa.first_occurrence("POST"), Some(40));
+    assert_eq!(sa.longest_common_substring("PUT /index.html 500"), Some("T /index.html "));
+    assert_eq!(string::SuffixAutomaton::build("abaaba").count_distinct_substrings(), 14);
+}
+
+#[test]
+fn test_suffix_array_sais() {
+    let text = "ACGTTGCAACGTACGTTTGA";
+    let sa = string::suffix_array_sais(text);
+    let mut expected: Vec<usize> = (0..text.len()).collect();
+    expected.sort_by(|a, b| text[*a..].cmp(&text[*b..]));
+    assert_eq!(sa, expected);
+
+    let lcp = string::lcp_array(text, &sa);
+    assert_eq!(lcp.iter().max(), Some(&5));
+    let table = string::LcpTable::build(text);
+    assert_eq!(table.lcp_array(), &lcp[..]);
+    assert_eq!(table.lcp(0, 8), Some(4));
+    assert_eq!(table.lcp(0, 12), Some(5));
+}
+
+#[test]
+fn test_suffix_tree() {
+    let texts = ["the quick brown fox", "a quick brown dog", "quick brown bears"];
+    let tree = string::SuffixTree::build(&texts);
+    assert!(tree.contains("brown d"));
+    assert!(!tree.contains("slow"));
+    assert_eq!(tree.find_all("quick"), vec![(0, 4), (1, 2), (2, 0)]);
+    assert_eq!(tree.longest_common_substring(3), Some("quick brown "));
+    assert_eq!(tree.longest_common_substring(2), Some(" quick brown "));
+    assert_eq!(tree.longest_repeated_substring(), Some(" quick brown "));
+}
+
+#[test]
+fn test_palindromes() {
+    let dna = "CCGATTACATTAGCA";
+    assert_eq!(string::longest_palindrome(dna), "CGATTACATTAGC");
+    let (d1, d2) = string::manacher(dna);
+    assert_eq!(d1[7], 7);
+    assert_eq!(d2[1], 1);
+
+    let tree = string::PalindromicTree::build("racecar");
+    assert_eq!(tree.count_distinct(), 7);
+    assert_eq!(tree.ending_at(), vec![1, 1, 1, 1, 2, 2, 2]);
+    assert_eq!(tree.prefix_distinct_counts(), vec![1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn test_edit_script() {
+    use librualg::string::EditOp;
+
+    let (cost, script) = string::levenshtein_edit_script("receive", "recieve", 1, 1, 1);
+    assert_eq!(cost, 2);
+    assert_eq!(script.iter().filter(|op| matches!(op, EditOp::Match(_, _))).count(), 5);
+    let (cost, _) = string::hirschberg("receive", "recieve", 1, 1, 1);
+    assert_eq!(cost, 2);
+    assert_eq!(string::damerau_levenshtein_distance("receive", "recieve"), 1);
+    let (cost, script) = string::hirschberg("naïve", "naive", 1, 1, 1);
+    assert_eq!(cost, 1);
+    assert_eq!(script[2], EditOp::Replace(2, 2));
+
+    let first = "ab".repeat(2000);
+    let second = "ba".repeat(2000);
+    let (cost, script) = string::hirschberg(&first, &second, 1, 1, 1);
+    assert_eq!(cost, 2);
+    assert_eq!(script.first(), Some(&EditOp::Delete(0)));
+    assert_eq!(script.last(), Some(&EditOp::Insert(3999)));
+}
+
+#[test]
+fn test_sequence_alignment() {
+    use librualg::string::{SubstitutionMatrix, GapPenalty};
+
+    let mut matrix = SubstitutionMatrix::new(5, -4);
+    // transitions are cheaper than transversions
+    matrix.set(b'A', b'G', -1);
+    matrix.set(b'C', b'T', -1);
+    let gap = GapPenalty { open: 10, extend: 1 };
+
+    let res = string::needleman_wunsch(b"ACCGTGAAGT", b"ACCATGAAGT", &matrix, gap);
+    assert_eq!(res.score, 9 * 5 - 1);
+    assert_eq!(res.first, b"ACCGTGAAGT");
+    assert_eq!(res.second, b"ACCATGAAGT");
+
+    let res = string::needleman_wunsch(b"ACGTAAAAAAACGT", b"ACGTACGT", &matrix, gap);
+    assert_eq!(res.second, b"ACGT------ACGT");
+    assert_eq!(res.score, 8 * 5 - 10 - 5);
+
+    let res = string::smith_waterman(b"TTTTTGATTACATTTT", b"CCGATTACACC", &matrix, gap);
+    assert_eq!(res.first, b"GATTACA");
+    assert_eq!(res.first_range, 5..12);
+    assert_eq!(res.second_range, 2..9);
+}
+
+#[test]
+fn test_aho_corasick_automaton() {
+    use librualg::string::{AhoCorasick, MatchKind};
+
+    let keywords = ["panic", "timeout", "refused", "time"];
+    let ac = AhoCorasick::with_options(&keywords, MatchKind::LeftmostLongest, true);
+    let log = "12:00 Connection REFUSED\n12:01 request timeout\n12:02 thread PANICKED\n12:03 time sync\n";
+    let found: Vec<&str> = ac.find_all(log).iter().map(|m| keywords[m.pattern]).collect();
+    assert_eq!(found, vec!["refused", "timeout", "panic", "time"]);
+
+    let mut searcher = ac.stream();
+    let mut streamed = vec![];
+    for chunk in log.as_bytes().chunks(3) {
+        streamed.extend(searcher.feed(chunk));
+    }
+    streamed.extend(searcher.finish());
+    assert_eq!(streamed, ac.find_all(log));
+    assert!(ac.is_match("TIMEOUT"));
+    assert!(!ac.is_match("all good"));
+}
+
+#[test]
+fn test_slice_algorithms() {
+    let trajectory = [3u32, 7, 7, 2, 3, 7, 7, 2, 3, 7];
+    assert_eq!(string::kmp_slice(&trajectory, &[7, 2, 3]), vec![2, 6]);
+    assert_eq!(string::kmp_first_slice(&trajectory, &[2, 3]), Some(3));
+    assert_eq!(string::minimum_period_slice(&trajectory), 4);
+    assert_eq!(string::z_function_slice(&trajectory)[4], 6);
+    assert_eq!(string::levenshtein_distance_slice(&trajectory[..4], &trajectory[4..8], 1, 1, 1), 0);
+
+    let text: Vec<char> = "naïve café".chars().collect();
+    assert_eq!(string::kmp_slice(&text, &['c', 'a', 'f', 'é']), vec![6]);
+    let (p, _) = string::suffix_array_slice(&text);
+    assert_eq!(p[0], 5);
+}
+
+#[test]
+fn test_rolling_hash_dedup() {
+    let records = ["user=42 action=login", "user=7 action=logout", "user=42 action=login", "user=7 action=login"];
+    let joined = records.join("\n");
+    let hash = string::RollingHash::build(&joined);
+    let mut starts = vec![0];
+    for (idx, ch) in joined.bytes().enumerate() {
+        if ch == b'\n' {
+            starts.push(idx + 1);
+        }
+    }
+    starts.push(joined.len() + 1);
+    let ranges: Vec<(usize, usize)> = starts.windows(2).map(|w| (w[0], w[1] - 1)).collect();
+    let mut unique: Vec<(usize, usize)> = vec![];
+    for &(l, r) in &ranges {
+        if !unique.iter().any(|&(ul, ur)| hash.substrings_equal(ul, ur, &hash, l, r)) {
+            unique.push((l, r));
+        }
+    }
+    assert_eq!(unique.len(), 3);
+
+    let found = string::rabin_karp(&joined, &["action=login", "user=7"]);
+    assert_eq!(found.iter().filter(|(_, idx)| *idx == 0).count(), 3);
+    assert_eq!(found.iter().filter(|(_, idx)| *idx == 1).count(), 2);
+}
+
+#[test]
+fn test_fm_index_corpus() {
+    let corpus = "GET /index.html 200\nGET /about.html 404\nPOST /login 302\nGET /index.html 304\n";
+    let (last, row) = string::bwt(corpus);
+    assert_eq!(string::inverse_bwt(&last, row), corpus.as_bytes().to_vec());
+
+    let index = string::FmIndex::with_sample_rate(corpus, 8);
+    assert_eq!(index.count("GET "), 3);
+    assert_eq!(index.locate("/index.html"), string::kmp(corpus, "/index.html"));
+    let loaded = string::FmIndex::from_bytes(&index.to_bytes()).unwrap();
+    assert_eq!(loaded.len(), corpus.len());
+    assert_eq!(loaded.locate(" 30"), vec![51, 71]);
+    assert!(!loaded.contains("DELETE"));
+}
+
+#[test]
+fn test_boyer_moore_searcher() {
+    let pattern = "needle in a haystack";
+    let text = "hay hay needle in a haystack hay needle in a haystack needle";
+    for searcher in &[string::Searcher::boyer_moore(pattern), string::Searcher::horspool(pattern)] {
+        assert_eq!(searcher.search(text), string::kmp(text, pattern));
+        assert_eq!(searcher.search_first(text), string::kmp_first(text, pattern));
+        assert_eq!(searcher.search_first("no needle here"), None);
+    }
+}
+
+#[test]
+fn test_typo_tolerant_search() {
+    let catalog = "red shoes;blue jacket;green umbrella;black trousers";
+    let found = string::approximate_search(catalog, "jaket", 1);
+    assert_eq!(found, vec![(21, 1)]);
+    assert_eq!(&catalog[15..21], "jacket");
+    assert_eq!(string::approximate_search(catalog, "umbrela", 0), vec![]);
+    assert!(string::approximate_search(catalog, "umbrela", 1).iter().any(|&(end, d)| end == 35 && d == 1));
+}
+
+#[test]
+fn test_name_similarity() {
+    let names = ["Jonathan Smith", "Jon Smith", "Smith Jonathan", "Joanna Smyth"];
+    let query = "Jonathon Smith";
+    let best = names.iter().max_by(|a, b| string::jaro_winkler(query, a).partial_cmp(&string::jaro_winkler(query, b)).unwrap()).unwrap();
+    assert_eq!(*best, "Jonathan Smith");
+    assert!((string::cosine_similarity(names[0], names[2]) - 1.0).abs() < 1e-9);
+    assert!(string::ngram_dice(query, names[0], 2) > string::ngram_dice(query, names[3], 2));
+    assert!(string::lcs_ratio(query, names[1]) > string::lcs_ratio(query, names[2]));
+    for name in &names {
+        for score in &[string::jaro(query, name), string::ngram_jaccard(query, name, 3), string::lcs_ratio(query, name)] {
+            assert!(*score >= 0.0 && *score <= 1.0);
+        }
+    }
+}
+
+#[test]
+fn test_periodicity_toolkit() {
+    // the same cyclic identifier written from different starting points
+    let ids = ["node-c:node-a:node-b:", "node-a:node-b:node-c:", "node-b:node-c:node-a:"];
+    let canonical: Vec<String> = ids.iter().map(|id| string::minimal_rotation(id)).collect();
+    assert!(canonical.iter().all(|id| *id == canonical[1]));
+
+    let telemetry = "ok;ok;ok;fail;fail;ok";
+    let long_runs: Vec<&str> = string::runs(telemetry).iter().filter(|run| run.period > 1).map(|run| &telemetry[run.start..run.end]).collect();
+    assert!(long_runs.contains(&"ok;ok;ok;"));
+    assert!(long_runs.contains(&";fail;fail;"));
+
+    assert_eq!(string::periods("abcabcab"), vec![3, 6, 8]);
+    assert_eq!(string::borders("abcabcab"), vec![2, 5]);
+    assert_eq!(string::lyndon_factorization("zyxabc").concat(), "zyxabc");
+}
+
+#[test]
+fn test_glob_path_filter() {
+    let paths = ["src/lib.rs", "src/string/mod.rs", "tests/string.rs", "README.md", "docs/Guide.MD", "target/debug/build.rs"];
+    let sources = string::Glob::new("src/**/*.rs");
+    let filtered: Vec<&str> = paths.iter().cloned().filter(|path| sources.is_match(path)).collect();
+    assert_eq!(filtered, vec!["src/lib.rs", "src/string/mod.rs"]);
+
+    let docs = string::Glob::with_options("**/*.md", true);
+    let filtered: Vec<&str> = paths.iter().cloned().filter(|path| docs.is_match(path)).collect();
+    assert_eq!(filtered, vec!["README.md", "docs/Guide.MD"]);
+
+    assert!(string::wildmatch("[!t]*/**", "src/string/mod.rs"));
+    assert!(!string::wildmatch("[!t]*/**", "target/debug/build.rs"));
+}
+
+#[test]
+fn test_regex_config_dsl() {
+    let line = string::Regex::new(r"^\s*([a-z_][a-z0-9_]*)\s*=\s*(?:(\d+)|'([^']*)')\s*(?:#.*)?$").unwrap();
+    let config = "port = 8080\nname = 'edge proxy' # public\n  retries=3\nbroken line\n";
+    let mut values = vec![];
+    for text in config.lines() {
+        match line.captures(text) {
+            Some(caps) => values.push((caps.get(1).unwrap(), caps.get(2).or_else(|| caps.get(3)).unwrap())),
+            None => assert!(!line.is_match(text)),
+        }
+    }
+    assert_eq!(values, vec![("port", "8080"), ("name", "edge proxy"), ("retries", "3")]);
+
+    let err = string::Regex::new("port = (\\d+").err().unwrap();
+    assert_eq!(err, string::RegexError::UnexpectedEnd);
+    let words = string::Regex::new(r"\w+").unwrap();
+    assert_eq!(words.find_all("a bc  def").len(), 3);
+}
+
+#[test]
+fn test_subsequences() {
+    let old = ["a", "b", "c", "d", "e"];
+    let new = ["a", "c", "x", "d", "e", "b"];
+    let common = string::hunt_szymanski(&old, &new);
+    assert_eq!(common.len(), 4);
+    assert_eq!(string::lcs_slice(&old, &new).len(), 4);
+    assert_eq!(string::lcs("kitten", "sitting"), "ittn");
+
+    let scores = [72, 85, 61, 90, 95, 40, 97];
+    let rising: Vec<i32> = string::lis(&scores).iter().map(|&i| scores[i]).collect();
+    assert_eq!(rising, vec![72, 85, 90, 95, 97]);
+    assert_eq!(string::lds(&scores).len(), 3);
+}
+
+#[test]
+fn test_unicode_positions() {
+    let text = "Москва и Київ";
+    let found = string::kmp_chars(text, "в");
+    assert_eq!(found, vec![string::TextPosition { byte: 8, char: 4 }, string::TextPosition { byte: 22, char: 12 }]);
+    assert_eq!(string::text_positions(text, &string::kmp(text, "в")), Some(found));
+    assert_eq!(string::substring_chars(text, 9, 13), Some("Київ"));
+    assert_eq!(string::minimum_string_period("ыыы"), "ы");
+    assert_eq!(string::distinct_substrings("ыы"), vec!["ы", "ыы"]);
+    assert_eq!(string::common_substring("Москва", "Киев"), Some("в"));
+    assert_eq!(string::SuffixAutomaton::build("東京都").longest_common_substring("京都府"), Some("京都"));
+    assert_eq!(string::SuffixTree::build(&["東京都", "京都府"]).longest_common_substring(2), Some("京都"));
+}